@@ -0,0 +1,47 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// Alignment of the viewBox along one axis of the viewport.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Min,
+    #[default]
+    Mid,
+    Max,
+}
+
+impl Display for Align {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            Align::Min => write!(fmt, "Min"),
+            Align::Mid => write!(fmt, "Mid"),
+            Align::Max => write!(fmt, "Max"),
+        }
+    }
+}
+
+/// How the viewBox is fitted in the viewport, written as `preserveAspectRatio`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AspectRatio {
+    /// Stretches the viewBox to fill the viewport.
+    None,
+    /// Scales the viewBox to fit entirely in the viewport, aligned along x and y.
+    Meet(Align, Align),
+    /// Scales the viewBox to cover the whole viewport, aligned along x and y.
+    Slice(Align, Align),
+}
+
+impl Default for AspectRatio {
+    fn default() -> Self {
+        AspectRatio::Meet(Align::Mid, Align::Mid)
+    }
+}
+
+impl Display for AspectRatio {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            AspectRatio::None => write!(fmt, "none"),
+            AspectRatio::Meet(x, y) => write!(fmt, "x{}Y{} meet", x, y),
+            AspectRatio::Slice(x, y) => write!(fmt, "x{}Y{} slice", x, y),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{precision::Num, Color, Precision, ViewBox};
+use std::fmt::{Result, Write};
+
+/// Fill of the area of the viewBox, drawn behind the content.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Background {
+    Color(Color),
+    /// Checkerboard showing transparent areas, with the size of its squares in the units of
+    /// the geometries.
+    Checkerboard(f64),
+}
+
+/// Border drawn on the inner edge of the viewBox.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame {
+    pub color: Color,
+    pub width: f32,
+}
+
+impl Background {
+    /// Identifier of the checkerboard `<pattern>` in the `<defs>` of the document.
+    pub(crate) const CHECKERBOARD_ID: &'static str = "geo-svg-checkerboard";
+
+    pub(crate) fn write_checkerboard_def(
+        out: &mut dyn Write,
+        size: f64,
+        precision: Option<Precision>,
+    ) -> Result {
+        write!(
+            out,
+            r#"<pattern id="{id}" width="{double}" height="{double}" patternUnits="userSpaceOnUse"><rect width="{double}" height="{double}" fill="white"/><path d="M 0 0 H {size} V {double} H {double} V {size} H 0 Z" fill="lightgray"/></pattern>"#,
+            id = Self::CHECKERBOARD_ID,
+            size = Num(size, precision),
+            double = Num(2.0 * size, precision),
+        )
+    }
+
+    pub(crate) fn write(
+        &self,
+        out: &mut dyn Write,
+        viewbox: &ViewBox,
+        precision: Option<Precision>,
+    ) -> Result {
+        write!(
+            out,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" stroke="none" fill=""#,
+            Num(viewbox.min_x(), precision),
+            Num(viewbox.min_y(), precision),
+            Num(viewbox.width(), precision),
+            Num(viewbox.height(), precision),
+        )?;
+        match self {
+            Background::Color(color) => write!(out, "{}", color)?,
+            Background::Checkerboard(_) => write!(out, "url(#{})", Self::CHECKERBOARD_ID)?,
+        }
+        write!(out, r#""/>"#)
+    }
+}
+
+impl Frame {
+    pub(crate) fn write(
+        &self,
+        out: &mut dyn Write,
+        viewbox: &ViewBox,
+        precision: Option<Precision>,
+    ) -> Result {
+        let inset = viewbox.with_margin(-(self.width as f64) / 2.0);
+        write!(
+            out,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            Num(inset.min_x(), precision),
+            Num(inset.min_y(), precision),
+            Num(inset.width(), precision),
+            Num(inset.height(), precision),
+            self.color,
+            self.width,
+        )
+    }
+}
@@ -1,11 +1,72 @@
+use crate::{named_colors, xml::Escaped};
+use std::error::Error;
 use std::fmt::{Display, Formatter, Result};
+use std::str::FromStr;
 
+/// Color of a SVG attribute, which can be parsed from the CSS color syntaxes.
+///
+/// ```
+/// use geo_svg::Color;
+///
+/// assert_eq!("#ff8800".parse(), Ok(Color::Hex(0xFF8800)));
+/// assert_eq!("rgba(0, 0, 0, 0.3)".parse(), Ok(Color::Rgba(0, 0, 0, 0.3)));
+/// assert_eq!("SteelBlue".parse(), Color::named("steelblue"));
+/// assert_eq!(Color::Hex(0xFF8800).to_string(), "#ff8800");
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Color {
-    Named(&'static str),
+    Named(NamedColor),
     Rgb(u8, u8, u8),
+    /// Red, green and blue components with an alpha between `0` and `1`.
+    Rgba(u8, u8, u8, f32),
+    /// `0xRRGGBB` value.
     Hex(u32),
     Hsl(u16, u8, u8),
+    /// Hue, saturation and lightness with an alpha between `0` and `1`.
+    Hsla(u16, u8, u8, f32),
+}
+
+/// One of the CSS named colors, such as `steelblue`.
+///
+/// [`NamedColor::new`] only accepts the names of the CSS color list, any other string has to go
+/// through [`NamedColor::new_unchecked`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NamedColor(&'static str);
+
+impl NamedColor {
+    /// Finds the CSS color called `name`, ignoring ASCII case.
+    pub fn new(name: &str) -> std::result::Result<Self, ParseColorError> {
+        named_colors::lookup(name.trim())
+            .map(|(name, _)| NamedColor(name))
+            .ok_or_else(|| ParseColorError {
+                input: name.to_string(),
+            })
+    }
+
+    /// Uses `name` as is, for colors known by the renderer but not in the CSS list.
+    ///
+    /// The name is escaped in the output, but renderers draw unknown names as black.
+    pub const fn new_unchecked(name: &'static str) -> Self {
+        NamedColor(name)
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl Display for NamedColor {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        write!(fmt, "{}", Escaped(self.0))
+    }
+}
+
+impl FromStr for NamedColor {
+    type Err = ParseColorError;
+
+    fn from_str(name: &str) -> std::result::Result<Self, Self::Err> {
+        NamedColor::new(name)
+    }
 }
 
 impl Display for Color {
@@ -13,10 +74,424 @@ impl Display for Color {
         match self {
             Color::Named(name) => write!(fmt, "{}", name),
             Color::Rgb(r, g, b) => write!(fmt, "rgb({},{},{})", r, g, b),
-            Color::Hex(hex) => write!(fmt, "{:#X}", hex),
+            Color::Rgba(r, g, b, a) => write!(fmt, "rgba({},{},{},{})", r, g, b, clamp_alpha(*a)),
+            Color::Hex(hex) => write!(fmt, "#{:06x}", hex & 0xFFFFFF),
             Color::Hsl(h, s, l) => {
                 write!(fmt, "hsl({},{}%,{}%)", h % 360, s.min(&100), l.min(&100))
             }
+            Color::Hsla(h, s, l, a) => write!(
+                fmt,
+                "hsla({},{}%,{}%,{})",
+                h % 360,
+                s.min(&100),
+                l.min(&100),
+                clamp_alpha(*a)
+            ),
+        }
+    }
+}
+
+/// Clamps an alpha value to `0..=1`, NaN being opaque.
+fn clamp_alpha(alpha: f32) -> f32 {
+    if alpha.is_nan() {
+        1.0
+    } else {
+        alpha.clamp(0.0, 1.0)
+    }
+}
+
+impl Color {
+    /// The CSS color called `name`, see [`NamedColor::new`].
+    pub fn named(name: &str) -> std::result::Result<Color, ParseColorError> {
+        NamedColor::new(name).map(Color::Named)
+    }
+
+    /// Red, green and blue components of this color, unknown named colors being resolved as
+    /// black.
+    pub(crate) fn rgb(&self) -> (u8, u8, u8) {
+        match *self {
+            Color::Named(name) => named_colors::lookup(name.name())
+                .map(|(_, rgb)| rgb)
+                .unwrap_or((0, 0, 0)),
+            Color::Rgb(r, g, b) | Color::Rgba(r, g, b, _) => (r, g, b),
+            Color::Hex(hex) => ((hex >> 16) as u8, (hex >> 8) as u8, hex as u8),
+            Color::Hsl(h, s, l) | Color::Hsla(h, s, l, _) => hsl_to_rgb(
+                (h % 360) as f64,
+                s.min(100) as f64 / 100.0,
+                l.min(100) as f64 / 100.0,
+            ),
+        }
+    }
+
+    /// Opacity of this color, `1` for the variants without alpha.
+    pub fn alpha(&self) -> f32 {
+        match *self {
+            Color::Rgba(_, _, _, a) | Color::Hsla(_, _, _, a) => clamp_alpha(a),
+            _ => 1.0,
+        }
+    }
+
+    /// Converts to [`Color::Rgb`], or [`Color::Rgba`] when the color is not opaque.
+    pub fn to_rgb(&self) -> Color {
+        let (r, g, b) = self.rgb();
+        Color::from_rgba(r, g, b, self.alpha())
+    }
+
+    /// Converts to [`Color::Hex`], dropping the alpha.
+    pub fn to_hex(&self) -> Color {
+        let (r, g, b) = self.rgb();
+        Color::Hex((r as u32) << 16 | (g as u32) << 8 | b as u32)
+    }
+
+    /// Converts to [`Color::Hsl`], or [`Color::Hsla`] when the color is not opaque.
+    pub fn to_hsl(&self) -> Color {
+        if let Color::Hsl(..) | Color::Hsla(..) = self {
+            return *self;
+        }
+        let (h, s, l) = rgb_to_hsl(self.rgb());
+        let percentage = |value: f64| (value * 100.0).round() as u8;
+        let (h, s, l) = (h.round() as u16 % 360, percentage(s), percentage(l));
+        match self.alpha() {
+            a if a < 1.0 => Color::Hsla(h, s, l, a),
+            _ => Color::Hsl(h, s, l),
+        }
+    }
+
+    /// This color with its alpha replaced by `alpha`, between `0` and `1`.
+    pub fn with_alpha(&self, alpha: f32) -> Color {
+        match *self {
+            Color::Hsl(h, s, l) | Color::Hsla(h, s, l, _) => Color::Hsla(h, s, l, alpha),
+            _ => {
+                let (r, g, b) = self.rgb();
+                Color::Rgba(r, g, b, alpha)
+            }
+        }
+    }
+
+    /// Increases the lightness by `amount`, between `0` and `1`, as the `lighten` of Sass.
+    pub fn lighten(&self, amount: f32) -> Color {
+        let (h, s, l) = rgb_to_hsl(self.rgb());
+        let (r, g, b) = hsl_to_rgb(h, s, (l + amount as f64).clamp(0.0, 1.0));
+        Color::from_rgba(r, g, b, self.alpha())
+    }
+
+    /// Decreases the lightness by `amount`, between `0` and `1`.
+    pub fn darken(&self, amount: f32) -> Color {
+        self.lighten(-amount)
+    }
+
+    /// Blends `other` into this color, `ratio` being the weight of `other` between `0` and `1`.
+    pub fn mix(&self, other: Color, ratio: f32) -> Color {
+        let ratio = ratio.clamp(0.0, 1.0);
+        let (r1, g1, b1) = self.rgb();
+        let (r2, g2, b2) = other.rgb();
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * ratio).round() as u8;
+        let alpha = self.alpha() + (other.alpha() - self.alpha()) * ratio;
+        Color::from_rgba(mix(r1, r2), mix(g1, g2), mix(b1, b2), alpha)
+    }
+
+    /// Relative luminance as defined by WCAG, from `0` for black to `1` for white.
+    pub fn luminance(&self) -> f64 {
+        let (r, g, b) = self.rgb();
+        let linear = |component: u8| {
+            let value = component as f64 / 255.0;
+            if value <= 0.04045 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+    }
+
+    /// WCAG contrast ratio with `other`, from `1` to `21`.
+    pub fn contrast(&self, other: Color) -> f64 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Black or white, whichever contrasts most with this color as a background.
+    pub fn readable_text_color(&self) -> Color {
+        let black = Color::Rgb(0, 0, 0);
+        let white = Color::Rgb(255, 255, 255);
+        if self.contrast(black) >= self.contrast(white) {
+            black
+        } else {
+            white
+        }
+    }
+
+    fn from_rgba(r: u8, g: u8, b: u8, a: f32) -> Color {
+        if a < 1.0 {
+            Color::Rgba(r, g, b, a)
+        } else {
+            Color::Rgb(r, g, b)
+        }
+    }
+}
+
+/// Converts a hue in degrees, and a saturation and lightness between `0` and `1`.
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> (u8, u8, u8) {
+    let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let sector = h / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = l - chroma / 2.0;
+    let component = |value: f64| ((value + m) * 255.0).round() as u8;
+    (component(r), component(g), component(b))
+}
+
+/// Converts to a hue in degrees, and a saturation and lightness between `0` and `1`.
+fn rgb_to_hsl((r, g, b): (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+    let l = (max + min) / 2.0;
+    if chroma == 0.0 {
+        return (0.0, 0.0, l);
+    }
+    let s = chroma / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        ((g - b) / chroma).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / chroma + 2.0
+    } else {
+        (r - g) / chroma + 4.0
+    };
+    (h * 60.0, s, l)
+}
+
+/// Error returned when a string is not a supported CSS color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColorError {
+    input: String,
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        write!(fmt, "invalid color `{}`", self.input)
+    }
+}
+
+impl Error for ParseColorError {}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parses a hex color (`#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`), a `rgb()`, `rgba()`,
+    /// `hsl()` or `hsla()` function, `transparent` or one of the CSS named colors.
+    fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+        let color = input.trim();
+        let parsed = if let Some(hex) = color.strip_prefix('#') {
+            parse_hex(hex)
+        } else if let Some((function, arguments)) = color
+            .strip_suffix(')')
+            .and_then(|color| color.split_once('('))
+        {
+            let arguments = arguments
+                .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+                .filter(|argument| !argument.is_empty())
+                .collect::<Vec<_>>();
+            match function.trim().to_ascii_lowercase().as_str() {
+                "rgb" | "rgba" => parse_rgb(&arguments),
+                "hsl" | "hsla" => parse_hsl(&arguments),
+                _ => None,
+            }
+        } else if color.eq_ignore_ascii_case("transparent") {
+            Some(Color::Rgba(0, 0, 0, 0.0))
+        } else {
+            named_colors::lookup(color).map(|(name, _)| Color::Named(NamedColor(name)))
+        };
+        parsed.ok_or_else(|| ParseColorError {
+            input: input.to_string(),
+        })
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |index: usize| u8::from_str_radix(&hex[index..index + 1], 16).ok();
+    let byte = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+    match hex.len() {
+        3 | 4 => {
+            let r = digit(0)? * 17;
+            let g = digit(1)? * 17;
+            let b = digit(2)? * 17;
+            match hex.len() {
+                3 => Some(Color::Rgb(r, g, b)),
+                _ => Some(Color::Rgba(r, g, b, (digit(3)? * 17) as f32 / 255.0)),
+            }
         }
+        6 => u32::from_str_radix(hex, 16).ok().map(Color::Hex),
+        8 => Some(Color::Rgba(
+            byte(0)?,
+            byte(2)?,
+            byte(4)?,
+            byte(6)? as f32 / 255.0,
+        )),
+        _ => None,
+    }
+}
+
+/// Parses a number, or a percentage of `max` when it ends with `%`.
+fn parse_number(argument: &str, max: f64) -> Option<f64> {
+    let value = match argument.strip_suffix('%') {
+        Some(percentage) => percentage.parse::<f64>().ok()? / 100.0 * max,
+        None => argument.parse::<f64>().ok()?,
+    };
+    Some(value).filter(|value| value.is_finite())
+}
+
+fn parse_alpha(argument: Option<&&str>) -> Option<Option<f32>> {
+    match argument {
+        Some(argument) => Some(Some(parse_number(argument, 1.0)?.clamp(0.0, 1.0) as f32)),
+        None => Some(None),
+    }
+}
+
+fn parse_rgb(arguments: &[&str]) -> Option<Color> {
+    if arguments.len() != 3 && arguments.len() != 4 {
+        return None;
+    }
+    let component = |index: usize| {
+        parse_number(arguments[index], 255.0).map(|value| value.round().clamp(0.0, 255.0) as u8)
+    };
+    let (r, g, b) = (component(0)?, component(1)?, component(2)?);
+    Some(match parse_alpha(arguments.get(3))? {
+        Some(a) => Color::Rgba(r, g, b, a),
+        None => Color::Rgb(r, g, b),
+    })
+}
+
+fn parse_hsl(arguments: &[&str]) -> Option<Color> {
+    if arguments.len() != 3 && arguments.len() != 4 {
+        return None;
+    }
+    let hue = arguments[0].strip_suffix("deg").unwrap_or(arguments[0]);
+    let h = hue.parse::<f64>().ok().filter(|h| h.is_finite())?;
+    let h = h.rem_euclid(360.0).round() as u16 % 360;
+    let percentage = |index: usize| {
+        let argument = arguments[index];
+        let value = argument.strip_suffix('%').unwrap_or(argument);
+        value
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .map(|value| value.round().clamp(0.0, 100.0) as u8)
+    };
+    let (s, l) = (percentage(1)?, percentage(2)?);
+    Some(match parse_alpha(arguments.get(3))? {
+        Some(a) => Color::Hsla(h, s, l, a),
+        None => Color::Hsl(h, s, l),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Color, NamedColor};
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("#f80".parse(), Ok(Color::Rgb(255, 136, 0)));
+        assert_eq!("#FF8800".parse(), Ok(Color::Hex(0xFF8800)));
+        assert_eq!(
+            "#ff880080".parse(),
+            Ok(Color::Rgba(255, 136, 0, 128.0 / 255.0))
+        );
+        assert_eq!("rgb(255 136 0)".parse(), Ok(Color::Rgb(255, 136, 0)));
+        assert_eq!("rgb(100%, 0%, 0%)".parse(), Ok(Color::Rgb(255, 0, 0)));
+        assert_eq!("rgba(0,0,0,30%)".parse(), Ok(Color::Rgba(0, 0, 0, 0.3)));
+        assert_eq!("hsl(120deg 50% 25%)".parse(), Ok(Color::Hsl(120, 50, 25)));
+        assert_eq!(
+            "hsla(-90, 100%, 50%, 0.5)".parse(),
+            Ok(Color::Hsla(270, 100, 50, 0.5))
+        );
+        assert_eq!(" Red ".parse(), Color::named("red"));
+        assert_eq!("transparent".parse(), Ok(Color::Rgba(0, 0, 0, 0.0)));
+        for invalid in [
+            "",
+            "#ff888",
+            "#gg8800",
+            "rgb(1,2)",
+            "hsl(a,b,c)",
+            "notacolor",
+        ] {
+            assert!(invalid.parse::<Color>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_named() {
+        assert_eq!(
+            NamedColor::new("DarkSlateGray").unwrap().name(),
+            "darkslategray"
+        );
+        assert!(NamedColor::new("gren").is_err());
+        assert!(Color::named("red\"/>").is_err());
+        let unchecked = Color::Named(NamedColor::new_unchecked("red\"/>"));
+        assert_eq!(unchecked.to_string(), "red&quot;/&gt;");
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Color::Hex(0xFF8800).to_string(), "#ff8800");
+        assert_eq!(Color::Hex(0xFF).to_string(), "#0000ff");
+        assert_eq!(Color::Rgba(1, 2, 3, 1.5).to_string(), "rgba(1,2,3,1)");
+        assert_eq!(
+            Color::Hsla(400, 50, 50, 0.25).to_string(),
+            "hsla(40,50%,50%,0.25)"
+        );
+    }
+
+    #[test]
+    fn test_rgb() {
+        assert_eq!(Color::named("steelblue").unwrap().rgb(), (70, 130, 180));
+        assert_eq!(Color::Hsl(0, 100, 50).rgb(), (255, 0, 0));
+        assert_eq!(Color::Hsl(210, 50, 40).rgb(), (51, 102, 153));
+    }
+
+    #[test]
+    fn test_conversions() {
+        let color = Color::Hex(0x336699);
+        assert_eq!(color.to_rgb(), Color::Rgb(51, 102, 153));
+        assert_eq!(color.to_hsl(), Color::Hsl(210, 50, 40));
+        assert_eq!(Color::Hsl(210, 50, 40).to_hex(), color);
+        assert_eq!(
+            Color::named("red").unwrap().with_alpha(0.5).to_hsl(),
+            Color::Hsla(0, 100, 50, 0.5)
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let color = Color::Rgb(51, 102, 153);
+        assert_eq!(color.lighten(0.2), Color::Rgb(102, 153, 204));
+        assert_eq!(color.darken(0.4), Color::Rgb(0, 0, 0));
+        assert_eq!(
+            Color::Rgb(0, 0, 0).mix(Color::Rgba(255, 255, 255, 0.0), 0.5),
+            Color::Rgba(128, 128, 128, 0.5)
+        );
+        assert_eq!(
+            Color::Rgb(0, 0, 0).contrast(Color::named("white").unwrap()),
+            21.0
+        );
+        assert_eq!(
+            Color::named("navy").unwrap().readable_text_color(),
+            Color::Rgb(255, 255, 255)
+        );
+        assert_eq!(
+            Color::named("yellow").unwrap().readable_text_color(),
+            Color::Rgb(0, 0, 0)
+        );
     }
 }
@@ -0,0 +1,320 @@
+use crate::{precision::Num, Color, Legend, Precision, Style};
+
+/// Perceptually uniform palettes for continuous values, from matplotlib.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Palette {
+    #[default]
+    Viridis,
+    Magma,
+    Inferno,
+    Plasma,
+    Cividis,
+}
+
+impl Palette {
+    fn stops(&self) -> &'static [u32] {
+        match self {
+            Palette::Viridis => &[
+                0x440154, 0x482878, 0x3E4989, 0x31688E, 0x26828E, 0x1F9E89, 0x35B779, 0x6ECE58,
+                0xB5DE2B, 0xFDE725,
+            ],
+            Palette::Magma => &[
+                0x000004, 0x180F3D, 0x440F76, 0x721F81, 0x9E2F7F, 0xCD4071, 0xF1605D, 0xFD9668,
+                0xFECA8D, 0xFCFDBF,
+            ],
+            Palette::Inferno => &[
+                0x000004, 0x1B0C41, 0x4A0C6B, 0x781C6D, 0xA52C60, 0xCF4446, 0xED6925, 0xFB9B06,
+                0xF7D13D, 0xFCFFA4,
+            ],
+            Palette::Plasma => &[
+                0x0D0887, 0x46039F, 0x7201A8, 0x9C179E, 0xBD3786, 0xD8576B, 0xED7953, 0xFB9F3A,
+                0xFDCA26, 0xF0F921,
+            ],
+            Palette::Cividis => &[
+                0x00204D, 0x00336F, 0x39486B, 0x575D6D, 0x707173, 0x8A8779, 0xA69D75, 0xC4B56C,
+                0xE4CF5B, 0xFFEA46,
+            ],
+        }
+    }
+}
+
+/// Palettes of distinct colors for categories, cycling when there are more categories than
+/// colors.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CategoricalPalette {
+    #[default]
+    Category10,
+    Tableau10,
+}
+
+impl CategoricalPalette {
+    fn colors(&self) -> &'static [u32] {
+        match self {
+            CategoricalPalette::Category10 => &[
+                0x1F77B4, 0xFF7F0E, 0x2CA02C, 0xD62728, 0x9467BD, 0x8C564B, 0xE377C2, 0x7F7F7F,
+                0xBCBD22, 0x17BECF,
+            ],
+            CategoricalPalette::Tableau10 => &[
+                0x4E79A7, 0xF28E2B, 0xE15759, 0x76B7B2, 0x59A14F, 0xEDC948, 0xB07AA1, 0xFF9DA7,
+                0x9C755F, 0xBAB0AC,
+            ],
+        }
+    }
+
+    /// Color of the category at `index`.
+    pub fn color(&self, index: usize) -> Color {
+        let colors = self.colors();
+        let (r, g, b) = Color::Hex(colors[index % colors.len()]).rgb();
+        Color::Rgb(r, g, b)
+    }
+
+    /// Style filling the category at `index` with its color.
+    pub fn fill(&self, index: usize) -> Style {
+        Style {
+            fill: Some(self.color(index)),
+            ..Style::default()
+        }
+    }
+}
+
+/// Gradient through evenly spaced colors, ignoring their alpha.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorRamp {
+    stops: Vec<(u8, u8, u8)>,
+}
+
+impl ColorRamp {
+    pub fn new(stops: &[Color]) -> Self {
+        Self {
+            stops: stops.iter().map(Color::rgb).collect(),
+        }
+    }
+
+    /// Color at `t`, between `0` for the first stop and `1` for the last one.
+    pub fn at(&self, t: f64) -> Color {
+        let last = match self.stops.last() {
+            Some(last) => *last,
+            None => return Color::Rgb(0, 0, 0),
+        };
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let position = t * (self.stops.len() - 1) as f64;
+        let index = position.floor() as usize;
+        let (r, g, b) = match self.stops.get(index + 1) {
+            Some(next) => {
+                let start = self.stops[index];
+                let ratio = position - index as f64;
+                let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * ratio).round() as u8;
+                (
+                    mix(start.0, next.0),
+                    mix(start.1, next.1),
+                    mix(start.2, next.2),
+                )
+            }
+            None => last,
+        };
+        Color::Rgb(r, g, b)
+    }
+}
+
+impl From<Palette> for ColorRamp {
+    fn from(palette: Palette) -> Self {
+        Self {
+            stops: palette
+                .stops()
+                .iter()
+                .map(|hex| Color::Hex(*hex).rgb())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Classes {
+    Linear,
+    /// Upper bounds of each class but the last one.
+    Quantile(Vec<f64>),
+}
+
+/// Maps numeric values to the colors of a ramp, to color features by a value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorScale {
+    ramp: ColorRamp,
+    min: f64,
+    max: f64,
+    classes: Classes,
+}
+
+impl ColorScale {
+    /// Interpolates the ramp between `min` and `max`, clamping values outside of them.
+    pub fn linear(ramp: impl Into<ColorRamp>, min: f64, max: f64) -> Self {
+        Self {
+            ramp: ramp.into(),
+            min,
+            max,
+            classes: Classes::Linear,
+        }
+    }
+
+    /// Splits `values` into `classes` classes holding as many values each, with a color of the
+    /// ramp per class.
+    pub fn quantile(
+        ramp: impl Into<ColorRamp>,
+        values: impl IntoIterator<Item = f64>,
+        classes: usize,
+    ) -> Self {
+        let mut values = values
+            .into_iter()
+            .filter(|value| !value.is_nan())
+            .collect::<Vec<_>>();
+        values.sort_by(f64::total_cmp);
+        let classes = classes.max(1);
+        let breaks = match values.len() {
+            0 => Vec::new(),
+            len => (1..classes)
+                .map(|class| values[class * len / classes])
+                .collect(),
+        };
+        Self {
+            ramp: ramp.into(),
+            min: values.first().copied().unwrap_or(0.0),
+            max: values.last().copied().unwrap_or(0.0),
+            classes: Classes::Quantile(breaks),
+        }
+    }
+
+    pub fn color(&self, value: f64) -> Color {
+        match &self.classes {
+            Classes::Linear => {
+                let range = self.max - self.min;
+                match range == 0.0 {
+                    true => self.ramp.at(0.0),
+                    false => self.ramp.at((value - self.min) / range),
+                }
+            }
+            Classes::Quantile(breaks) => {
+                let class = breaks.iter().filter(|upper| value >= **upper).count();
+                self.class_color(class, breaks.len() + 1)
+            }
+        }
+    }
+
+    /// Style filling a feature with the color of `value`.
+    pub fn fill(&self, value: f64) -> Style {
+        Style {
+            fill: Some(self.color(value)),
+            ..Style::default()
+        }
+    }
+
+    fn class_color(&self, class: usize, classes: usize) -> Color {
+        match classes {
+            1 => self.ramp.at(0.0),
+            _ => self.ramp.at(class as f64 / (classes - 1) as f64),
+        }
+    }
+
+    /// Legend entries with the color of each class, or of evenly spaced values for a linear
+    /// scale.
+    pub fn entries(&self) -> Vec<(String, Style)> {
+        let label = |value: f64| Num(value, Some(Precision::Significant(3))).to_string();
+        let fill = |color: Color| Style {
+            fill: Some(color),
+            ..Style::default()
+        };
+        match &self.classes {
+            Classes::Linear => (0..5)
+                .map(|step| {
+                    let value = self.min + (self.max - self.min) * step as f64 / 4.0;
+                    (label(value), fill(self.color(value)))
+                })
+                .collect(),
+            Classes::Quantile(breaks) => {
+                let bounds = std::iter::once(self.min)
+                    .chain(breaks.iter().copied())
+                    .chain(std::iter::once(self.max))
+                    .collect::<Vec<_>>();
+                bounds
+                    .windows(2)
+                    .enumerate()
+                    .map(|(class, bounds)| {
+                        (
+                            format!("{} – {}", label(bounds[0]), label(bounds[1])),
+                            fill(self.class_color(class, bounds.len() - 1)),
+                        )
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+impl Legend {
+    /// Adds the entries of a color scale after the named layers.
+    pub fn with_color_scale(mut self, scale: &ColorScale) -> Self {
+        self.entries.extend(scale.entries());
+        self
+    }
+
+    /// Adds an entry for each category, colored by the palette in the same order.
+    pub fn with_categories(
+        mut self,
+        palette: CategoricalPalette,
+        names: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.entries.extend(
+            names
+                .into_iter()
+                .enumerate()
+                .map(|(index, name)| (name.into(), palette.fill(index))),
+        );
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CategoricalPalette, ColorRamp, ColorScale, Palette};
+    use crate::Color;
+
+    #[test]
+    fn test_ramp() {
+        let ramp = ColorRamp::new(&[
+            Color::Rgb(0, 0, 0),
+            Color::Hex(0xFF0000),
+            Color::Rgb(255, 255, 255),
+        ]);
+        assert_eq!(ramp.at(0.0), Color::Rgb(0, 0, 0));
+        assert_eq!(ramp.at(0.25), Color::Rgb(128, 0, 0));
+        assert_eq!(ramp.at(0.75), Color::Rgb(255, 128, 128));
+        assert_eq!(ramp.at(2.0), Color::Rgb(255, 255, 255));
+        assert_eq!(
+            ColorRamp::from(Palette::Viridis).at(0.0),
+            Color::Rgb(68, 1, 84)
+        );
+    }
+
+    #[test]
+    fn test_quantile() {
+        let scale = ColorScale::quantile(
+            ColorRamp::new(&[Color::Rgb(0, 0, 0), Color::Rgb(200, 200, 200)]),
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 100.0],
+            3,
+        );
+        assert_eq!(scale.color(1.5), Color::Rgb(0, 0, 0));
+        assert_eq!(scale.color(3.5), Color::Rgb(100, 100, 100));
+        assert_eq!(scale.color(50.0), Color::Rgb(200, 200, 200));
+        let labels = scale
+            .entries()
+            .into_iter()
+            .map(|(label, _)| label)
+            .collect::<Vec<_>>();
+        assert_eq!(labels, vec!["1 – 3", "3 – 5", "5 – 100"]);
+    }
+
+    #[test]
+    fn test_categorical() {
+        let palette = CategoricalPalette::Category10;
+        assert_eq!(palette.color(1), Color::Rgb(255, 127, 14));
+        assert_eq!(palette.color(11), palette.color(1));
+    }
+}
@@ -0,0 +1,19 @@
+/// Corner of a document where an overlay such as the legend is placed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Corner {
+    pub(crate) fn is_left(&self) -> bool {
+        matches!(self, Corner::TopLeft | Corner::BottomLeft)
+    }
+
+    pub(crate) fn is_top(&self) -> bool {
+        matches!(self, Corner::TopLeft | Corner::TopRight)
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{precision::Num, Arrows, Background, PointMarker, Precision, Style, ViewBox};
+use std::fmt::{Result, Write};
+
+/// Identifier of the clip path restricting the content to the viewBox.
+pub(crate) const CLIP_ID: &str = "geo-svg-clip";
+
+/// Reusable elements written once in the `<defs>` of a document.
+#[derive(Default)]
+pub(crate) struct Defs {
+    /// Markers with a definition, by identifier.
+    markers: Vec<(String, PointMarker)>,
+    arrow: bool,
+    clip: Option<ViewBox>,
+    checkerboard: Option<f64>,
+}
+
+impl Defs {
+    /// Registers the definitions needed to render items with `style`.
+    pub(crate) fn add_style(&mut self, style: &Style) {
+        if let Some(marker) = &style.marker {
+            if let Some(id) = marker.def_id() {
+                if !self.markers.iter().any(|(other, _)| *other == id) {
+                    self.markers.push((id, marker.clone()));
+                }
+            }
+        }
+        if let Some(arrows) = style.arrows {
+            self.arrow |= !arrows.is_empty();
+        }
+    }
+
+    /// Registers a clip path restricting the content to `viewbox`.
+    pub(crate) fn add_clip(&mut self, viewbox: ViewBox) {
+        self.clip = Some(viewbox);
+    }
+
+    /// Registers the pattern of a checkerboard background with squares of `size`.
+    pub(crate) fn add_checkerboard(&mut self, size: f64) {
+        self.checkerboard = Some(size);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.markers.is_empty() && !self.arrow && self.clip.is_none() && self.checkerboard.is_none()
+    }
+
+    pub(crate) fn write(&self, out: &mut dyn Write, precision: Option<Precision>) -> Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+        write!(out, "<defs>")?;
+        for (_, marker) in &self.markers {
+            marker.write_def(out)?;
+        }
+        if self.arrow {
+            Arrows::write_def(out)?;
+        }
+        if let Some(size) = self.checkerboard {
+            Background::write_checkerboard_def(out, size, precision)?;
+        }
+        if let Some(clip) = self.clip {
+            write!(
+                out,
+                r#"<clipPath id="{}"><rect x="{}" y="{}" width="{}" height="{}"/></clipPath>"#,
+                CLIP_ID,
+                Num(clip.min_x(), precision),
+                Num(clip.min_y(), precision),
+                Num(clip.width(), precision),
+                Num(clip.height(), precision),
+            )?;
+        }
+        write!(out, "</defs>")
+    }
+}
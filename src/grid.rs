@@ -0,0 +1,262 @@
+use crate::{precision::Num, Color, NamedColor, Orientation, Precision, ViewBox};
+use std::fmt::{Result, Write};
+
+/// Maximum number of grid lines along each axis, smaller spacings being widened.
+const MAX_LINES: f64 = 1000.0;
+
+/// Coordinate grid drawn over the content, with labelled ticks along the top and left edges.
+///
+/// Line widths are in screen pixels, so they do not change with the zoom level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Grid {
+    /// Distance between grid lines, chosen from the viewBox when not set.
+    pub spacing: Option<f64>,
+    pub color: Color,
+    pub stroke_width: f32,
+    /// Whether the x and y axes are drawn with a thicker line.
+    pub axes: bool,
+    pub labels: bool,
+    /// Size of the labels in the units of the geometries, chosen from the viewBox when not set.
+    pub font_size: Option<f64>,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self {
+            spacing: None,
+            color: Color::Named(NamedColor::new_unchecked("gray")),
+            stroke_width: 0.5,
+            axes: true,
+            labels: true,
+            font_size: None,
+        }
+    }
+}
+
+impl Grid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_spacing(mut self, spacing: f64) -> Self {
+        self.spacing = Some(spacing);
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_stroke_width(mut self, stroke_width: f32) -> Self {
+        self.stroke_width = stroke_width;
+        self
+    }
+
+    pub fn with_axes(mut self, axes: bool) -> Self {
+        self.axes = axes;
+        self
+    }
+
+    pub fn with_labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f64) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Spacing of the grid lines for the given viewBox.
+    ///
+    /// A spacing giving more than a thousand lines along an axis is widened to a round step
+    /// giving at most that many.
+    pub fn spacing(&self, viewbox: &ViewBox) -> f64 {
+        let size = viewbox.width().max(viewbox.height());
+        match self
+            .spacing
+            .filter(|spacing| *spacing > 0.0 && spacing.is_finite())
+        {
+            Some(spacing) if size / spacing <= MAX_LINES => spacing,
+            Some(_) => nice_step(size / MAX_LINES),
+            None => nice_step(size / 8.0),
+        }
+    }
+
+    pub(crate) fn write(
+        &self,
+        out: &mut dyn Write,
+        viewbox: &ViewBox,
+        orientation: Orientation,
+        precision: Option<Precision>,
+    ) -> Result {
+        if viewbox.width() <= 0.0 && viewbox.height() <= 0.0 {
+            return Ok(());
+        }
+        let spacing = self.spacing(viewbox);
+        let xs = ticks(viewbox.min_x(), viewbox.max_x(), spacing);
+        let ys = ticks(viewbox.min_y(), viewbox.max_y(), spacing);
+        let n = |value: f64| Num(value, precision);
+        write!(out, r#"<g class="geo-svg-grid"><path d=""#)?;
+        let mut separator = "";
+        for x in &xs {
+            write!(
+                out,
+                "{}M {} {} V {}",
+                separator,
+                n(*x),
+                n(viewbox.min_y()),
+                n(viewbox.max_y())
+            )?;
+            separator = " ";
+        }
+        for y in &ys {
+            write!(
+                out,
+                "{}M {} {} H {}",
+                separator,
+                n(viewbox.min_x()),
+                n(*y),
+                n(viewbox.max_x())
+            )?;
+            separator = " ";
+        }
+        write!(
+            out,
+            r#"" fill="none" stroke="{}" stroke-width="{}" vector-effect="non-scaling-stroke"/>"#,
+            self.color, self.stroke_width
+        )?;
+        if self.axes {
+            let mut axes = String::new();
+            if viewbox.min_x() <= 0.0 && 0.0 <= viewbox.max_x() {
+                write!(axes, "M 0 {} V {}", n(viewbox.min_y()), n(viewbox.max_y()))?;
+            }
+            if viewbox.min_y() <= 0.0 && 0.0 <= viewbox.max_y() {
+                if !axes.is_empty() {
+                    axes.push(' ');
+                }
+                write!(axes, "M {} 0 H {}", n(viewbox.min_x()), n(viewbox.max_x()))?;
+            }
+            if !axes.is_empty() {
+                write!(
+                    out,
+                    r#"<path d="{}" fill="none" stroke="{}" stroke-width="{}" vector-effect="non-scaling-stroke"/>"#,
+                    axes,
+                    self.color,
+                    self.stroke_width * 2.0
+                )?;
+            }
+        }
+        if self.labels {
+            let font_size = self
+                .font_size
+                .unwrap_or_else(|| viewbox.width().min(viewbox.height()).max(spacing) / 40.0);
+            let decimals = decimals(spacing);
+            let label = |value: f64| Num(value, Some(Precision::Decimals(decimals)));
+            write!(
+                out,
+                r#"<g fill="{}" stroke="none" font-size="{}">"#,
+                self.color,
+                n(font_size)
+            )?;
+            for x in &xs {
+                write!(
+                    out,
+                    r#"<text x="{}" y="{}" dx="{}" dominant-baseline="hanging">{}</text>"#,
+                    n(*x),
+                    n(viewbox.min_y()),
+                    n(font_size / 4.0),
+                    label(*x)
+                )?;
+            }
+            for y in &ys {
+                let value = match orientation {
+                    Orientation::YDown => *y,
+                    Orientation::YUp => -*y,
+                };
+                write!(
+                    out,
+                    r#"<text x="{}" y="{}" dx="{}" dy="{}">{}</text>"#,
+                    n(viewbox.min_x()),
+                    n(*y),
+                    n(font_size / 4.0),
+                    n(-font_size / 4.0),
+                    label(value)
+                )?;
+            }
+            write!(out, "</g>")?;
+        }
+        write!(out, "</g>")
+    }
+}
+
+/// Rounds `step` up to 1, 2 or 5 times a power of ten.
+fn nice_step(step: f64) -> f64 {
+    if step <= 0.0 || !step.is_finite() {
+        return 1.0;
+    }
+    let magnitude = 10f64.powf(step.log10().floor());
+    let normalized = step / magnitude;
+    let nice = if normalized <= 1.0 {
+        1.0
+    } else if normalized <= 2.0 {
+        2.0
+    } else if normalized <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}
+
+/// Number of decimals of the shortest representation of `spacing`, so that all its significant
+/// digits are kept in the labels.
+fn decimals(spacing: f64) -> u8 {
+    let formatted = spacing.to_string();
+    let decimals = formatted
+        .split_once('.')
+        .map_or(0, |(_, decimals)| decimals.len());
+    decimals.min(15) as u8
+}
+
+/// Multiples of `spacing` between `min` and `max`.
+fn ticks(min: f64, max: f64, spacing: f64) -> Vec<f64> {
+    let first = (min / spacing).ceil() as i64;
+    let last = (max / spacing).floor() as i64;
+    (first..=last).map(|index| index as f64 * spacing).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decimals, nice_step, ticks, Grid};
+    use crate::ViewBox;
+
+    #[test]
+    fn test_nice_step() {
+        assert_eq!(nice_step(0.13), 0.2);
+        assert_eq!(nice_step(3.0), 5.0);
+        assert_eq!(nice_step(70.0), 100.0);
+        assert_eq!(nice_step(1000.0), 1000.0);
+    }
+
+    #[test]
+    fn test_decimals() {
+        assert_eq!(decimals(0.25), 2);
+        assert_eq!(decimals(2.5), 1);
+        assert_eq!(decimals(100.0), 0);
+        assert_eq!(decimals(0.1 + 0.2), 15);
+    }
+
+    #[test]
+    fn test_spacing_clamped() {
+        let viewbox = ViewBox::new(0.0, 0.0, 1e6, 1e6);
+        assert_eq!(Grid::new().with_spacing(1e-9).spacing(&viewbox), 1000.0);
+        assert_eq!(Grid::new().with_spacing(2500.0).spacing(&viewbox), 2500.0);
+    }
+
+    #[test]
+    fn test_ticks() {
+        assert_eq!(ticks(-1.5, 3.2, 1.0), vec![-1.0, 0.0, 1.0, 2.0, 3.0]);
+    }
+}
@@ -0,0 +1,237 @@
+use crate::{
+    precision::{widen, Num},
+    xml::Escaped,
+    Color, NamedColor, Style, ViewBox,
+};
+use geo_types::{Coord, CoordNum, LineString, Polygon};
+use num_traits::NumCast;
+use std::fmt::{Display, Formatter, Result, Write};
+
+/// Ratio between the average width of a character and the font size, used to estimate text bounds.
+pub(crate) const CHAR_WIDTH_RATIO: f64 = 0.6;
+
+/// Horizontal alignment of a label relative to its anchor, written as `text-anchor`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextAnchor {
+    Start,
+    #[default]
+    Middle,
+    End,
+}
+
+impl Display for TextAnchor {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            TextAnchor::Start => write!(fmt, "start"),
+            TextAnchor::Middle => write!(fmt, "middle"),
+            TextAnchor::End => write!(fmt, "end"),
+        }
+    }
+}
+
+/// Text drawn next to each item of a [`Svg`](crate::Svg).
+///
+/// Sizes and offsets are in the units of the geometries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    pub text: String,
+    /// Position of the label, defaults to the anchor of the labelled item.
+    pub anchor: Option<Coord<f64>>,
+    pub font_size: f32,
+    pub offset: (f64, f64),
+    pub color: Color,
+    /// Outline drawn behind the text to keep it readable, with its color and width.
+    ///
+    /// The halo is solid and opaque, whatever the stroke of the layer it labels.
+    pub halo: Option<(Color, f32)>,
+    pub text_anchor: TextAnchor,
+}
+
+impl Label {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            anchor: None,
+            font_size: 10.0,
+            offset: (0.0, 0.0),
+            color: Color::Named(NamedColor::new_unchecked("black")),
+            halo: None,
+            text_anchor: TextAnchor::default(),
+        }
+    }
+
+    pub fn with_anchor(mut self, anchor: Coord<f64>) -> Self {
+        self.anchor = Some(anchor);
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    pub fn with_offset(mut self, dx: f64, dy: f64) -> Self {
+        self.offset = (dx, dy);
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_halo(mut self, color: Color, width: f32) -> Self {
+        self.halo = Some((color, width));
+        self
+    }
+
+    pub fn with_text_anchor(mut self, text_anchor: TextAnchor) -> Self {
+        self.text_anchor = text_anchor;
+        self
+    }
+
+    /// Position of the text in SVG coordinates, for an item anchored at `anchor`.
+    fn position(&self, anchor: Option<Coord<f64>>, style: &Style) -> Option<Coord<f64>> {
+        let anchor = self.anchor.or(anchor)?;
+        Some(style.orientation().apply(Coord {
+            x: anchor.x + self.offset.0,
+            y: anchor.y + self.offset.1,
+        }))
+    }
+
+    pub(crate) fn write(
+        &self,
+        out: &mut dyn Write,
+        anchor: Option<Coord<f64>>,
+        style: &Style,
+    ) -> Result {
+        let position = match self.position(anchor, style) {
+            Some(position) => position,
+            None => return Ok(()),
+        };
+        write!(
+            out,
+            r#"<text x="{}" y="{}" font-size="{}" text-anchor="{}" dominant-baseline="central" fill="{}""#,
+            Num(position.x, style.precision),
+            Num(position.y, style.precision),
+            self.font_size,
+            self.text_anchor,
+            self.color,
+        )?;
+        match self.halo {
+            Some((color, width)) => write!(
+                out,
+                r#" stroke="{}" stroke-width="{}" stroke-opacity="1" stroke-dasharray="none" stroke-linejoin="round" paint-order="stroke""#,
+                color, width
+            )?,
+            None => write!(out, r#" stroke="none""#)?,
+        }
+        write!(out, ">{}</text>", Escaped(&self.text))
+    }
+
+    /// Estimated bounds of the text, as fonts are not known when writing the SVG.
+    pub(crate) fn viewbox(&self, anchor: Option<Coord<f64>>, style: &Style) -> ViewBox {
+        let position = match self.position(anchor, style) {
+            Some(position) => position,
+            None => return ViewBox::default(),
+        };
+        let font_size = widen(self.font_size);
+        let halo = self.halo.map(|(_, width)| widen(width)).unwrap_or(0.0);
+        let width = CHAR_WIDTH_RATIO * font_size * self.text.chars().count() as f64;
+        let min_x = match self.text_anchor {
+            TextAnchor::Start => position.x,
+            TextAnchor::Middle => position.x - width / 2.0,
+            TextAnchor::End => position.x - width,
+        };
+        ViewBox::new(
+            min_x - halo,
+            position.y - font_size / 2.0 - halo,
+            min_x + width + halo,
+            position.y + font_size / 2.0 + halo,
+        )
+    }
+}
+
+impl From<&str> for Label {
+    fn from(text: &str) -> Self {
+        Label::new(text)
+    }
+}
+
+impl From<String> for Label {
+    fn from(text: String) -> Self {
+        Label::new(text)
+    }
+}
+
+pub(crate) fn to_f64<T: CoordNum>(coord: Coord<T>) -> Coord<f64> {
+    Coord {
+        x: NumCast::from(coord.x).unwrap_or(0.0),
+        y: NumCast::from(coord.y).unwrap_or(0.0),
+    }
+}
+
+/// Point halfway along a line string.
+pub(crate) fn line_string_midpoint<T: CoordNum>(line_string: &LineString<T>) -> Option<Coord<f64>> {
+    let length = |start: Coord<f64>, end: Coord<f64>| (end.x - start.x).hypot(end.y - start.y);
+    let segments = || {
+        line_string
+            .lines()
+            .map(|line| (to_f64(line.start), to_f64(line.end)))
+    };
+    let mut remaining = segments()
+        .map(|(start, end)| length(start, end))
+        .sum::<f64>()
+        / 2.0;
+    for (start, end) in segments() {
+        let segment_length = length(start, end);
+        if segment_length >= remaining && segment_length > 0.0 {
+            let ratio = remaining / segment_length;
+            return Some(Coord {
+                x: start.x + (end.x - start.x) * ratio,
+                y: start.y + (end.y - start.y) * ratio,
+            });
+        }
+        remaining -= segment_length;
+    }
+    line_string.0.first().map(|coord| to_f64(*coord))
+}
+
+/// Point inside a polygon, at the middle of the widest interior span of the horizontal line
+/// crossing the middle of its bounds.
+pub(crate) fn polygon_interior_point<T: CoordNum>(polygon: &Polygon<T>) -> Option<Coord<f64>> {
+    let exterior = &polygon.exterior().0;
+    let first = to_f64(*exterior.first()?);
+    let (min_y, max_y) = exterior
+        .iter()
+        .fold((first.y, first.y), |(min, max), coord| {
+            let y = to_f64(*coord).y;
+            (min.min(y), max.max(y))
+        });
+    let y = (min_y + max_y) / 2.0;
+    let mut crossings = std::iter::once(polygon.exterior())
+        .chain(polygon.interiors().iter())
+        .flat_map(|ring| ring.lines())
+        .filter_map(|line| {
+            let (start, end) = (to_f64(line.start), to_f64(line.end));
+            if (start.y <= y) != (end.y <= y) {
+                Some(start.x + (y - start.y) * (end.x - start.x) / (end.y - start.y))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    crossings.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    crossings
+        .chunks_exact(2)
+        .max_by(|a, b| {
+            (a[1] - a[0])
+                .partial_cmp(&(b[1] - b[0]))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map(|span| Coord {
+            x: (span[0] + span[1]) / 2.0,
+            y,
+        })
+        .or(Some(first))
+}
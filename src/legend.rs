@@ -0,0 +1,158 @@
+use crate::{
+    label::CHAR_WIDTH_RATIO, precision::Num, xml::Escaped, Corner, Precision, Style, ViewBox,
+};
+use std::fmt::{Result, Write};
+
+/// Box listing the named layers of a document with a swatch of their style.
+///
+/// The legend is placed next to the content in the given corner, extending the viewBox, or
+/// inside the viewBox when it is fixed.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Legend {
+    pub corner: Corner,
+    /// Size of the text in the units of the geometries, chosen from the viewBox when not set.
+    pub font_size: Option<f64>,
+    /// Entries listed after the named layers.
+    pub entries: Vec<(String, Style)>,
+}
+
+impl Legend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_corner(mut self, corner: Corner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f64) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Adds an entry which does not come from a named layer.
+    pub fn with_entry(mut self, name: impl Into<String>, style: Style) -> Self {
+        self.entries.push((name.into(), style));
+        self
+    }
+
+    fn font_size(&self, data: &ViewBox) -> f64 {
+        self.font_size
+            .unwrap_or_else(|| data.width().max(data.height()) / 40.0)
+    }
+
+    /// Style of the swatch drawn for an entry, points being drawn with the size of the text.
+    pub(crate) fn swatch_style(&self, style: &Style, data: &ViewBox) -> Style {
+        Style {
+            radius: Some((self.font_size(data) / 3.0) as f32),
+            orientation: None,
+            ..style.clone()
+        }
+    }
+
+    /// Bounds of the legend box for the given entries, next to `data` or inside it if `inside`.
+    pub(crate) fn bounds(
+        &self,
+        entries: &[(String, Style)],
+        data: &ViewBox,
+        inside: bool,
+    ) -> ViewBox {
+        if entries.is_empty() {
+            return ViewBox::default();
+        }
+        let font_size = self.font_size(data);
+        let longest = entries
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0) as f64;
+        let width = font_size * (3.0 + CHAR_WIDTH_RATIO * longest);
+        let height = font_size * (1.0 + 1.5 * entries.len() as f64);
+        let gap = font_size;
+        let min_x = match (self.corner.is_left(), inside) {
+            (true, false) => data.min_x() - gap - width,
+            (true, true) => data.min_x() + gap,
+            (false, false) => data.max_x() + gap,
+            (false, true) => data.max_x() - gap - width,
+        };
+        let min_y = match (self.corner.is_top(), inside) {
+            (true, false) => data.min_y(),
+            (true, true) => data.min_y() + gap,
+            (false, false) => data.max_y() - height,
+            (false, true) => data.max_y() - gap - height,
+        };
+        ViewBox::new(min_x, min_y, min_x + width, min_y + height)
+    }
+
+    pub(crate) fn write(
+        &self,
+        out: &mut dyn Write,
+        entries: &[(String, Style)],
+        data: &ViewBox,
+        inside: bool,
+        precision: Option<Precision>,
+    ) -> Result {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let bounds = self.bounds(entries, data, inside);
+        let font_size = self.font_size(data);
+        let n = |value: f64| Num(value, precision);
+        write!(
+            out,
+            r#"<g class="geo-svg-legend"><rect x="{}" y="{}" width="{}" height="{}" fill="white" fill-opacity="0.8" stroke="gray" stroke-width="{}"/>"#,
+            n(bounds.min_x()),
+            n(bounds.min_y()),
+            n(bounds.width()),
+            n(bounds.height()),
+            n(font_size / 20.0),
+        )?;
+        let x = bounds.min_x() + font_size / 2.0;
+        for (index, (name, style)) in entries.iter().enumerate() {
+            let y = bounds.min_y() + font_size * (1.25 + 1.5 * index as f64);
+            let style = Style {
+                precision,
+                ..self.swatch_style(style, data)
+            };
+            if let Some(marker) = &style.marker {
+                marker.write(
+                    out,
+                    geo_types::Coord {
+                        x: x + font_size / 2.0,
+                        y,
+                    },
+                    &style,
+                )?;
+            } else if style.fill.is_some() {
+                write!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}"{}/>"#,
+                    n(x),
+                    n(y - font_size * 0.35),
+                    n(font_size),
+                    n(font_size * 0.7),
+                    style
+                )?;
+            } else {
+                write!(
+                    out,
+                    r#"<path d="M {} {} H {}" fill="none"{}/>"#,
+                    n(x),
+                    n(y),
+                    n(x + font_size),
+                    style
+                )?;
+            }
+            write!(
+                out,
+                r#"<text x="{}" y="{}" font-size="{}" dominant-baseline="central" fill="black" stroke="none">{}</text>"#,
+                n(x + font_size * 1.5),
+                n(y),
+                n(font_size),
+                Escaped(name)
+            )?;
+        }
+        write!(out, "</g>")
+    }
+}
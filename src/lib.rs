@@ -16,49 +16,81 @@
 //! The [`to_svg`] method is provided by the [`ToSvg`] trait which is implemented for all [geo-types](https://docs.rs/geo-types/0.4.3/geo_types/).
 //!
 //! ```
-//! # fn main() {
-//! use geo_types::{Coordinate, Line, Point};
+//! # fn main() -> Result<(), geo_svg::ParseColorError> {
+//! use geo_types::{Coord, Line, Point};
 //! use geo_svg::{Color, ToSvg};
 //! let point = Point::new(10.0, 28.1);
 //! let line = Line::new(
-//!     Coordinate { x: 114.19, y: 22.26 },
-//!     Coordinate { x: 15.93, y: -15.76 },
+//!     Coord { x: 114.19, y: 22.26 },
+//!     Coord { x: 15.93, y: -15.76 },
 //! );
 //!
 //! let svg = point
 //!     .to_svg()
 //!     .with_radius(2.0)
 //!     .and(line.to_svg().with_stroke_width(2.5))
-//!     .with_fill_color(Color::Named("red"))
+//!     .with_fill_color(Color::named("red")?)
 //!     .with_stroke_color(Color::Rgb(200, 0, 100))
 //!     .with_fill_opacity(0.7);
 //!
 //! println!("{}", svg);
-//! # assert_eq!(svg.to_string(), r#"<svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet" viewBox="7 -18.26 109.69 49.36"><circle cx="10.0" cy="28.1" r="2" fill="red" fill-opacity="0.7" stroke="rgb(200,0,100)"/><path d="M 114.19 22.26 L 15.93 -15.76" fill="red" fill-opacity="0.7" stroke="rgb(200,0,100)" stroke-width="2.5"/></svg>"#);
+//! # assert_eq!(svg.to_string(), r#"<svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet" viewBox="7 -18.26 109.69 49.36"><g fill="red" fill-opacity="0.7" stroke="rgb(200,0,100)"><circle cx="10.0" cy="28.1" r="2"/><g stroke-width="2.5"><path d="M 114.19 22.26 L 15.93 -15.76"/></g></g></svg>"#);
+//! # Ok(())
 //! # }
 //! ```
 //!
 //! ## Result
 //!
 //! ```xml
-//! <svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet" viewBox="7 -18.26 109.69 49.36"><circle cx="10" cy="28.1" r="2" fill="red" fill-opacity="0.7" stroke="rgb(200,0,100)"/><path d="M 114.19 22.26 L 15.93 -15.76" fill="red" fill-opacity="0.7" stroke="rgb(200,0,100)" stroke-width="2.5"/></svg>
+//! <svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet" viewBox="7 -18.26 109.69 49.36"><g fill="red" fill-opacity="0.7" stroke="rgb(200,0,100)"><circle cx="10.0" cy="28.1" r="2"/><g stroke-width="2.5"><path d="M 114.19 22.26 L 15.93 -15.76"/></g></g></svg>
 //! ```
 //!
 //! [`ToSvg`]: svg/trait.ToSvg.html
 //! [`to_svg`]: svg/trait.ToSvg.html#method.to_svg
 
+mod aspect_ratio;
+mod background;
 mod color;
+mod color_scale;
+mod corner;
+mod defs;
+mod grid;
+mod label;
+mod legend;
+mod marker;
+mod named_colors;
+mod orientation;
+mod precision;
+mod scale_bar;
+mod size;
+mod stroke;
 mod style;
+mod styled;
 mod svg;
 mod svg_impl;
 mod to_svg;
 mod to_svg_str;
+mod vertices;
 mod viewbox;
+mod xml;
 
+pub use aspect_ratio::{Align, AspectRatio};
+pub use background::{Background, Frame};
 pub use color::*;
+pub use color_scale::{CategoricalPalette, ColorRamp, ColorScale, Palette};
+pub use corner::Corner;
+pub use grid::Grid;
+pub use label::{Label, TextAnchor};
+pub use legend::Legend;
+pub use marker::PointMarker;
+pub use orientation::Orientation;
+pub use precision::Precision;
+pub use scale_bar::{DistanceUnit, NorthArrow, ScaleBar};
+pub use size::{Size, Unit};
+pub use stroke::{Arrows, LineCap, LineJoin};
 pub use style::*;
-pub use svg::Svg;
-pub use svg_impl::*;
+pub use styled::{Styled, ToSvgStyled};
+pub use svg::{OwnedSvg, Svg, SvgItem};
 pub use to_svg::*;
 pub use to_svg_str::*;
-pub use viewbox::ViewBox;
+pub use viewbox::{Margin, ViewBox};
@@ -0,0 +1,179 @@
+use crate::{
+    precision::{widen, Num},
+    xml::Escaped,
+    Precision, Style,
+};
+use geo_types::{Coord, CoordNum};
+use num_traits::NumCast;
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+use std::fmt::{Result, Write};
+
+/// Shape drawn for points, sized by [`Style::radius`].
+///
+/// The outline of all shapes is measured in the units of the geometries. [`PointMarker::Cross`]
+/// and [`PointMarker::X`] have nothing to fill, so they take the fill color when no stroke color
+/// is set.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum PointMarker {
+    #[default]
+    Circle,
+    Square,
+    Diamond,
+    Triangle,
+    Cross,
+    X,
+    Star,
+    /// Custom SVG content drawn in a `[-1, 1]` square, scaled to the point radius.
+    Symbol {
+        id: String,
+        content: String,
+    },
+}
+
+impl PointMarker {
+    /// Identifier of the `<defs>` element used by this marker, if any.
+    ///
+    /// Circles and squares are written inline, other shapes are defined once at unit size and
+    /// scaled to the radius of each point.
+    pub(crate) fn def_id(&self) -> Option<String> {
+        let name = match self {
+            PointMarker::Circle | PointMarker::Square => return None,
+            PointMarker::Symbol { id, .. } => return Some(id.clone()),
+            PointMarker::Diamond => "diamond",
+            PointMarker::Triangle => "triangle",
+            PointMarker::Cross => "cross",
+            PointMarker::X => "x",
+            PointMarker::Star => "star",
+        };
+        Some(format!("geo-svg-{}", name))
+    }
+
+    /// Whether this marker is drawn with a `<use>` of its `<defs>` element.
+    pub(crate) fn is_symbol(&self) -> bool {
+        !matches!(self, PointMarker::Circle | PointMarker::Square)
+    }
+
+    /// Whether this marker is only made of lines, with nothing to fill.
+    ///
+    /// These shapes are stroked with the `color` property, set to the stroke or the fill of the
+    /// layer, so that they are visible when only one of them is set.
+    pub(crate) fn is_stroke_only(&self) -> bool {
+        matches!(self, PointMarker::Cross | PointMarker::X)
+    }
+
+    /// Writes the `<defs>` content of this marker, a `<symbol>` drawn in a `[-1, 1]` square.
+    pub(crate) fn write_def(&self, out: &mut dyn Write) -> Result {
+        let id = match self.def_id() {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+        write!(
+            out,
+            r#"<symbol id="{}" viewBox="-1 -1 2 2" overflow="visible">"#,
+            Escaped(&id)
+        )?;
+        if let PointMarker::Symbol { content, .. } = self {
+            return write!(out, "{}</symbol>", content);
+        }
+        let points: Vec<(f64, f64)> = match self {
+            PointMarker::Diamond => vec![(0.0, -1.0), (1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)],
+            PointMarker::Triangle => {
+                let (sin, cos) = (PI / 6.0).sin_cos();
+                vec![(0.0, -1.0), (cos, sin), (-cos, sin)]
+            }
+            PointMarker::Star => (0..10)
+                .map(|index| {
+                    let length = if index % 2 == 0 { 1.0 } else { 0.382 };
+                    let (sin, cos) = (index as f64 * PI / 5.0 - PI / 2.0).sin_cos();
+                    (length * cos, length * sin)
+                })
+                .collect(),
+            _ => vec![],
+        };
+        write!(out, r#"<path d=""#)?;
+        match self {
+            PointMarker::Cross => write!(out, "M -1 0 L 1 0 M 0 -1 L 0 1")?,
+            PointMarker::X => {
+                let a = FRAC_1_SQRT_2;
+                write!(
+                    out,
+                    "M {} {} L {} {} M {} {} L {} {}",
+                    -a, -a, a, a, -a, a, a, -a
+                )?
+            }
+            _ => {
+                let n = |value: f64| Num(value, Some(Precision::Decimals(6)));
+                for (index, (x, y)) in points.iter().enumerate() {
+                    let command = if index == 0 { "M" } else { " L" };
+                    write!(out, "{} {} {}", command, n(*x), n(*y))?;
+                }
+                write!(out, " Z")?;
+            }
+        }
+        if self.is_stroke_only() {
+            write!(out, r#"" fill="none" stroke="currentColor"/></symbol>"#)
+        } else {
+            write!(out, r#""/></symbol>"#)
+        }
+    }
+
+    /// Writes the element drawing this marker centered on `coord`.
+    ///
+    /// Symbols are scaled to the radius, so their stroke width is divided by it to be measured in
+    /// the units of the geometries, like the one of circles and squares.
+    pub(crate) fn write<T: CoordNum>(
+        &self,
+        out: &mut dyn Write,
+        coord: Coord<T>,
+        style: &Style,
+    ) -> Result {
+        let radius = style.radius();
+        let r = widen(radius);
+        let x: f64 = NumCast::from(coord.x).unwrap_or(0.0);
+        let y: f64 = NumCast::from(coord.y).unwrap_or(0.0);
+        let n = |value: f64| Num(value, style.precision);
+        match self {
+            PointMarker::Circle => write!(
+                out,
+                r#"<circle cx="{}" cy="{}" r="{}"{}/>"#,
+                Num(coord.x, style.precision),
+                Num(coord.y, style.precision),
+                match style.precision {
+                    Some(_) => n(r).to_string(),
+                    None => radius.to_string(),
+                },
+                style
+            ),
+            PointMarker::Square => write!(
+                out,
+                r#"<rect x="{}" y="{}" width="{}" height="{}"{}/>"#,
+                n(x - r),
+                n(y - r),
+                n(2.0 * r),
+                n(2.0 * r),
+                style
+            ),
+            _ => {
+                write!(
+                    out,
+                    r##"<use href="#{}" x="{}" y="{}" width="{}" height="{}""##,
+                    Escaped(&self.def_id().unwrap_or_default()),
+                    n(x - r),
+                    n(y - r),
+                    n(2.0 * r),
+                    n(2.0 * r),
+                )?;
+                if self.is_stroke_only() {
+                    if let Some(color) = style.stroke_color.or(style.fill) {
+                        write!(out, r#" color="{}""#, color)?;
+                    }
+                }
+                let scaled = Style {
+                    stroke_width: Some(style.stroke_width.unwrap_or(1.0) / radius),
+                    ..style.clone()
+                };
+                write!(out, "{}/>", scaled)
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{label::to_f64, ViewBox};
+use geo_types::{Coord, CoordNum};
+
+/// Direction of the y axis of the input coordinates.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// The y axis points down, as in SVG: coordinates are written unchanged.
+    #[default]
+    YDown,
+    /// The y axis points up, as in geographic or cartesian coordinates: the y axis is flipped.
+    YUp,
+}
+
+impl Orientation {
+    /// Maps a coordinate to the SVG coordinate system.
+    ///
+    /// The flip is done in `f64`, so that unsigned coordinates can be flipped too.
+    pub fn apply<T: CoordNum>(&self, coord: Coord<T>) -> Coord<f64> {
+        let coord = to_f64(coord);
+        match self {
+            Orientation::YDown => coord,
+            Orientation::YUp => Coord {
+                x: coord.x,
+                y: 0.0 - coord.y,
+            },
+        }
+    }
+
+    /// Maps a viewBox given in the coordinates of the geometries to the SVG coordinate system.
+    pub(crate) fn apply_viewbox(&self, viewbox: ViewBox) -> ViewBox {
+        match self {
+            Orientation::YDown => viewbox,
+            Orientation::YUp => ViewBox::new(
+                viewbox.min_x(),
+                0.0 - viewbox.max_y(),
+                viewbox.max_x(),
+                0.0 - viewbox.min_y(),
+            ),
+        }
+    }
+}
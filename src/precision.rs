@@ -0,0 +1,112 @@
+use crate::ViewBox;
+use num_traits::NumCast;
+use std::fmt::{Debug, Display, Formatter, Result};
+
+/// Numeric precision of the coordinates written in the SVG output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    /// Fixed number of decimal places.
+    Decimals(u8),
+    /// Number of significant digits relative to the largest dimension of the document viewBox.
+    ///
+    /// When used outside of a [`Svg`](crate::Svg), each number keeps this many significant digits.
+    Significant(u8),
+}
+
+impl Precision {
+    /// Converts significant digits to decimal places for the given viewBox.
+    pub fn resolve(&self, viewbox: &ViewBox) -> Self {
+        match self {
+            Precision::Decimals(_) => *self,
+            Precision::Significant(digits) => {
+                let size = viewbox.width().max(viewbox.height());
+                Precision::Decimals(Self::decimals(*digits, size))
+            }
+        }
+    }
+
+    fn decimals(digits: u8, magnitude: f64) -> u8 {
+        if magnitude == 0.0 || !magnitude.is_finite() {
+            return digits;
+        }
+        let integer_digits = magnitude.abs().log10().floor() as i32 + 1;
+        (digits as i32 - integer_digits).clamp(0, u8::MAX as i32) as u8
+    }
+
+    fn decimals_for(&self, value: f64) -> usize {
+        match self {
+            Precision::Decimals(decimals) => *decimals as usize,
+            Precision::Significant(digits) => Self::decimals(*digits, value) as usize,
+        }
+    }
+}
+
+/// Converts a `f32` parameter to `f64` through its shortest decimal form, so that `0.1` is
+/// written as `0.1` rather than `0.10000000149011612`.
+pub(crate) fn widen(value: f32) -> f64 {
+    value.to_string().parse().unwrap_or(value as f64)
+}
+
+/// Formats a number with an optional precision, trimming trailing zeros.
+///
+/// Without precision the number is written with its `Debug` representation.
+pub(crate) struct Num<T>(pub T, pub Option<Precision>);
+
+impl<T: NumCast + Debug + Copy> Display for Num<T> {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        let precision = match self.1 {
+            Some(precision) => precision,
+            None => return write!(fmt, "{:?}", self.0),
+        };
+        let value: f64 = match NumCast::from(self.0) {
+            Some(value) => value,
+            None => return write!(fmt, "{:?}", self.0),
+        };
+        let formatted = format!("{:.*}", precision.decimals_for(value), value);
+        let trimmed = if formatted.contains('.') {
+            formatted.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            &formatted
+        };
+        if trimmed == "-0" {
+            write!(fmt, "0")
+        } else {
+            write!(fmt, "{}", trimmed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{widen, Num, Precision};
+    use crate::ViewBox;
+
+    #[test]
+    fn test_decimals() {
+        let precision = Some(Precision::Decimals(3));
+        assert_eq!(Num(0.1 + 0.2, precision).to_string(), "0.3");
+        assert_eq!(Num(1.23456, precision).to_string(), "1.235");
+        assert_eq!(Num(-0.0001, precision).to_string(), "0");
+        assert_eq!(Num(12, precision).to_string(), "12");
+        assert_eq!(Num(0.1 + 0.2, None).to_string(), "0.30000000000000004");
+    }
+
+    #[test]
+    fn test_widen() {
+        assert_eq!(widen(0.1), 0.1);
+        assert_eq!(widen(4.0 / 3.0), 1.3333334);
+    }
+
+    #[test]
+    fn test_significant() {
+        let viewbox = ViewBox::new(650_000.0, 5_400_000.0, 652_000.0, 5_401_500.0);
+        assert_eq!(
+            Precision::Significant(6).resolve(&viewbox),
+            Precision::Decimals(2)
+        );
+        assert_eq!(
+            Num(123.456, Some(Precision::Significant(4))).to_string(),
+            "123.5"
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{precision::Num, Corner, Orientation, Precision, ViewBox};
+use std::fmt::{self, Display, Formatter, Result, Write};
+
+/// Unit of distance of projected coordinates.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceUnit {
+    #[default]
+    Meters,
+    Kilometers,
+    Feet,
+}
+
+impl Display for DistanceUnit {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        let symbol = match self {
+            DistanceUnit::Meters => "m",
+            DistanceUnit::Kilometers => "km",
+            DistanceUnit::Feet => "ft",
+        };
+        write!(fmt, "{}", symbol)
+    }
+}
+
+/// Bar showing a round distance, about a quarter of the width of the viewBox.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleBar {
+    /// Unit of the coordinates of the geometries.
+    pub unit: DistanceUnit,
+    pub corner: Corner,
+    /// Size of the text in the units of the geometries, chosen from the viewBox when not set.
+    pub font_size: Option<f64>,
+}
+
+/// Arrow pointing to increasing y coordinates, up when the orientation is [`Orientation::YUp`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct NorthArrow {
+    pub corner: Corner,
+    /// Height of the arrow in the units of the geometries, chosen from the viewBox when not
+    /// set.
+    pub size: Option<f64>,
+}
+
+impl ScaleBar {
+    pub fn new(unit: DistanceUnit) -> Self {
+        Self {
+            unit,
+            corner: Corner::BottomLeft,
+            font_size: None,
+        }
+    }
+
+    pub fn with_corner(mut self, corner: Corner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f64) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Length of the bar in the units of the geometries.
+    pub fn length(&self, viewbox: &ViewBox) -> f64 {
+        nice_length(viewbox.width() / 4.0)
+    }
+
+    /// Text of the bar, switching from meters to kilometers for long distances.
+    fn text(&self, length: f64) -> String {
+        match self.unit {
+            DistanceUnit::Meters if length >= 1000.0 => {
+                format!("{} {}", length / 1000.0, DistanceUnit::Kilometers)
+            }
+            unit => format!("{} {}", length, unit),
+        }
+    }
+
+    pub(crate) fn write(
+        &self,
+        out: &mut dyn Write,
+        viewbox: &ViewBox,
+        precision: Option<Precision>,
+    ) -> Result {
+        if viewbox.width() <= 0.0 {
+            return Ok(());
+        }
+        let font_size = self
+            .font_size
+            .unwrap_or_else(|| viewbox.width().min(viewbox.height()).max(0.0) / 40.0);
+        let length = self.length(viewbox);
+        let height = font_size / 2.0;
+        let padding = font_size;
+        let x = match self.corner.is_left() {
+            true => viewbox.min_x() + padding,
+            false => viewbox.max_x() - padding - length,
+        };
+        let y = match self.corner.is_top() {
+            true => viewbox.min_y() + padding + font_size * 1.25,
+            false => viewbox.max_y() - padding - height,
+        };
+        let n = |value: f64| Num(value, precision);
+        write!(
+            out,
+            r#"<g class="geo-svg-scale-bar" stroke="black" stroke-width="{}"><rect x="{}" y="{}" width="{}" height="{}" fill="black"/><rect x="{}" y="{}" width="{}" height="{}" fill="white"/>"#,
+            n(height / 10.0),
+            n(x),
+            n(y),
+            n(length / 2.0),
+            n(height),
+            n(x + length / 2.0),
+            n(y),
+            n(length / 2.0),
+            n(height),
+        )?;
+        write!(
+            out,
+            r#"<g fill="black" stroke="none" font-size="{}"><text x="{}" y="{y}">0</text><text x="{}" y="{y}" text-anchor="end">{}</text></g></g>"#,
+            n(font_size),
+            n(x),
+            n(x + length),
+            self.text(length),
+            y = n(y - font_size / 4.0),
+        )
+    }
+}
+
+impl NorthArrow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_corner(mut self, corner: Corner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    pub fn with_size(mut self, size: f64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub(crate) fn write(
+        &self,
+        out: &mut dyn Write,
+        viewbox: &ViewBox,
+        orientation: Orientation,
+        precision: Option<Precision>,
+    ) -> Result {
+        let size = self
+            .size
+            .unwrap_or_else(|| viewbox.width().min(viewbox.height()).max(0.0) / 10.0);
+        if size <= 0.0 {
+            return Ok(());
+        }
+        let half = size / 2.0;
+        let font_size = size / 3.0;
+        let padding = font_size;
+        // The "N" is drawn beyond the tip of the arrow.
+        let text_height = font_size * 1.25;
+        let sign = match orientation {
+            Orientation::YUp => 1.0,
+            Orientation::YDown => -1.0,
+        };
+        let x = match self.corner.is_left() {
+            true => viewbox.min_x() + padding + half,
+            false => viewbox.max_x() - padding - half,
+        };
+        let top = match self.corner.is_top() {
+            true => viewbox.min_y() + padding,
+            false => viewbox.max_y() - padding - size - text_height,
+        };
+        let y = match orientation {
+            Orientation::YUp => top + text_height + half,
+            Orientation::YDown => top + half,
+        };
+        let n = |value: f64| Num(value, precision);
+        write!(
+            out,
+            r#"<g class="geo-svg-north-arrow" fill="black" stroke="none"><path d="M {} {} L {} {} L {} {} L {} {} Z"/>"#,
+            n(x),
+            n(y - sign * half),
+            n(x + half * 0.6),
+            n(y + sign * half),
+            n(x),
+            n(y + sign * half * 0.5),
+            n(x - half * 0.6),
+            n(y + sign * half),
+        )?;
+        write!(
+            out,
+            r#"<text x="{}" y="{}" font-size="{}" text-anchor="middle" dominant-baseline="central">N</text></g>"#,
+            n(x),
+            n(y - sign * (half + text_height / 2.0)),
+            n(font_size),
+        )
+    }
+}
+
+/// Rounds `length` down to 1, 2 or 5 times a power of ten.
+fn nice_length(length: f64) -> f64 {
+    if length <= 0.0 || !length.is_finite() {
+        return 1.0;
+    }
+    let magnitude = 10f64.powf(length.log10().floor());
+    let normalized = length / magnitude;
+    let nice = if normalized >= 5.0 {
+        5.0
+    } else if normalized >= 2.0 {
+        2.0
+    } else {
+        1.0
+    };
+    nice * magnitude
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{nice_length, DistanceUnit, ScaleBar};
+
+    #[test]
+    fn test_nice_length() {
+        assert_eq!(nice_length(0.13), 0.1);
+        assert_eq!(nice_length(3.0), 2.0);
+        assert_eq!(nice_length(740.0), 500.0);
+        assert_eq!(nice_length(1000.0), 1000.0);
+    }
+
+    #[test]
+    fn test_text() {
+        let scale_bar = ScaleBar::new(DistanceUnit::Meters);
+        assert_eq!(scale_bar.text(500.0), "500 m");
+        assert_eq!(scale_bar.text(2000.0), "2 km");
+        assert_eq!(ScaleBar::new(DistanceUnit::Feet).text(5000.0), "5000 ft");
+    }
+}
@@ -0,0 +1,62 @@
+use crate::ViewBox;
+use std::fmt::{Display, Formatter, Result};
+
+/// Unit of the `width` and `height` of a document.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    #[default]
+    Px,
+    Mm,
+    Cm,
+    In,
+    Pt,
+}
+
+impl Display for Unit {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            Unit::Px => write!(fmt, "px"),
+            Unit::Mm => write!(fmt, "mm"),
+            Unit::Cm => write!(fmt, "cm"),
+            Unit::In => write!(fmt, "in"),
+            Unit::Pt => write!(fmt, "pt"),
+        }
+    }
+}
+
+/// Output size of a document, written as the `width` and `height` of the `<svg>` element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Size {
+    /// Fixed width and height, the content is fitted according to the aspect ratio.
+    Fixed { width: f64, height: f64, unit: Unit },
+    /// Fixed width, the height follows the aspect ratio of the viewBox.
+    Width(f64, Unit),
+    /// Fixed height, the width follows the aspect ratio of the viewBox.
+    Height(f64, Unit),
+    /// Fixed scale, as a number of output units per unit of the geometries.
+    Scale(f64, Unit),
+}
+
+impl Size {
+    /// Width, height and unit of a document showing the given viewBox.
+    pub fn resolve(&self, viewbox: &ViewBox) -> (f64, f64, Unit) {
+        let (view_width, view_height) = (viewbox.width(), viewbox.height());
+        let ratio = |numerator: f64, denominator: f64| {
+            if denominator > 0.0 {
+                numerator / denominator
+            } else {
+                1.0
+            }
+        };
+        match *self {
+            Size::Fixed {
+                width,
+                height,
+                unit,
+            } => (width, height, unit),
+            Size::Width(width, unit) => (width, width * ratio(view_height, view_width), unit),
+            Size::Height(height, unit) => (height * ratio(view_width, view_height), height, unit),
+            Size::Scale(scale, unit) => (view_width * scale, view_height * scale, unit),
+        }
+    }
+}
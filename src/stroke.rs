@@ -0,0 +1,108 @@
+use std::fmt::{Display, Formatter, Result, Write};
+
+/// Shape at the end of open subpaths, written as `stroke-linecap`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineCap {
+    Butt,
+    Round,
+    Square,
+}
+
+impl Display for LineCap {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            LineCap::Butt => write!(fmt, "butt"),
+            LineCap::Round => write!(fmt, "round"),
+            LineCap::Square => write!(fmt, "square"),
+        }
+    }
+}
+
+/// Shape at the corners of paths, written as `stroke-linejoin`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineJoin {
+    Miter,
+    Round,
+    Bevel,
+}
+
+impl Display for LineJoin {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            LineJoin::Miter => write!(fmt, "miter"),
+            LineJoin::Round => write!(fmt, "round"),
+            LineJoin::Bevel => write!(fmt, "bevel"),
+        }
+    }
+}
+
+/// Arrowheads drawn on the vertices of paths, to show their direction.
+///
+/// Arrows are scaled with the stroke width. They take the color of the stroke with the SVG 2
+/// `context-stroke` keyword, viewers which do not support it draw them in black.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Arrows {
+    pub start: bool,
+    /// Arrows on the interior vertices of paths, drawn with `marker-mid`.
+    ///
+    /// They are placed on the vertices, not in the middle of the segments, so a [`Line`] or a
+    /// two-point [`LineString`] has none.
+    ///
+    /// [`Line`]: geo_types::Line
+    /// [`LineString`]: geo_types::LineString
+    pub vertices: bool,
+    pub end: bool,
+}
+
+impl Arrows {
+    /// Identifier of the arrow `<marker>` in the `<defs>` of the document.
+    pub(crate) const ID: &'static str = "geo-svg-arrow";
+
+    /// Only an arrow at the end of paths.
+    pub fn end() -> Self {
+        Self {
+            end: true,
+            ..Self::default()
+        }
+    }
+
+    /// Arrows on every vertex but the first one.
+    pub fn all() -> Self {
+        Self {
+            start: false,
+            vertices: true,
+            end: true,
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        !(self.start || self.vertices || self.end)
+    }
+
+    pub(crate) fn write_def(out: &mut dyn Write) -> Result {
+        write!(
+            out,
+            r#"<marker id="{}" viewBox="0 0 10 10" refX="10" refY="5" markerUnits="strokeWidth" markerWidth="4" markerHeight="4" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 Z" fill="black" stroke="none" style="fill:context-stroke"/></marker>"#,
+            Self::ID
+        )
+    }
+}
+
+impl Display for Arrows {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        let value = |enabled: bool| {
+            if enabled {
+                format!("url(#{})", Self::ID)
+            } else {
+                "none".to_string()
+            }
+        };
+        write!(
+            fmt,
+            r#" marker-start="{}" marker-mid="{}" marker-end="{}""#,
+            value(self.start),
+            value(self.vertices),
+            value(self.end)
+        )
+    }
+}
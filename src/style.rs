@@ -1,27 +1,97 @@
-use crate::Color;
+use crate::{Arrows, Color, LineCap, LineJoin, Orientation, PointMarker, Precision};
 use std::fmt::{Display, Formatter, Result};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Style {
+    /// Opacity of the whole group, which SVG multiplies with the one of the enclosing groups.
     pub opacity: Option<f32>,
     pub fill: Option<Color>,
     pub fill_opacity: Option<f32>,
     pub stroke_color: Option<Color>,
     pub stroke_width: Option<f32>,
     pub stroke_opacity: Option<f32>,
-    pub radius: f32,
+    pub stroke_dasharray: Option<Vec<f32>>,
+    pub stroke_linecap: Option<LineCap>,
+    pub stroke_linejoin: Option<LineJoin>,
+    pub stroke_miterlimit: Option<f32>,
+    pub arrows: Option<Arrows>,
+    pub radius: Option<f32>,
+    pub marker: Option<PointMarker>,
+    /// Orientation of the document, passed down from the root [`Svg`](crate::Svg) to the items.
+    ///
+    /// Set it with [`Svg::with_orientation`](crate::Svg::with_orientation), the value of the
+    /// nested styles is ignored.
+    pub orientation: Option<Orientation>,
+    pub precision: Option<Precision>,
+    pub vertex_indices: Option<bool>,
 }
 
-impl Default for Style {
-    fn default() -> Self {
+impl Style {
+    /// Fills the attributes not set in this style with the ones of `parent`.
+    ///
+    /// The opacity is multiplied with the one of `parent`, as nested groups are composited.
+    pub fn inherit(&self, parent: &Style) -> Self {
+        Self {
+            opacity: match (self.opacity, parent.opacity) {
+                (Some(opacity), Some(parent)) => Some(opacity * parent),
+                (opacity, parent) => opacity.or(parent),
+            },
+            fill: self.fill.or(parent.fill),
+            fill_opacity: self.fill_opacity.or(parent.fill_opacity),
+            stroke_color: self.stroke_color.or(parent.stroke_color),
+            stroke_width: self.stroke_width.or(parent.stroke_width),
+            stroke_opacity: self.stroke_opacity.or(parent.stroke_opacity),
+            stroke_dasharray: self
+                .stroke_dasharray
+                .clone()
+                .or_else(|| parent.stroke_dasharray.clone()),
+            stroke_linecap: self.stroke_linecap.or(parent.stroke_linecap),
+            stroke_linejoin: self.stroke_linejoin.or(parent.stroke_linejoin),
+            stroke_miterlimit: self.stroke_miterlimit.or(parent.stroke_miterlimit),
+            arrows: self.arrows.or(parent.arrows),
+            radius: self.radius.or(parent.radius),
+            marker: self.marker.clone().or_else(|| parent.marker.clone()),
+            orientation: parent.orientation.or(self.orientation),
+            precision: self.precision.or(parent.precision),
+            vertex_indices: self.vertex_indices.or(parent.vertex_indices),
+        }
+    }
+
+    /// Returns a copy of this style keeping only the parameters which are not SVG attributes.
+    ///
+    /// Elements inside a `<g>` get their attributes from the group, so they are rendered with this.
+    pub fn without_attributes(&self) -> Self {
+        Self {
+            radius: self.radius,
+            marker: self.marker.clone(),
+            orientation: self.orientation,
+            precision: self.precision,
+            vertex_indices: self.vertex_indices,
+            ..Self::default()
+        }
+    }
+
+    /// Point radius, 1 by default.
+    pub fn radius(&self) -> f32 {
+        self.radius.unwrap_or(1.0)
+    }
+
+    /// Orientation of the y axis, [`Orientation::YDown`] by default.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation.unwrap_or_default()
+    }
+
+    /// Whether vertex indices are annotated, `false` by default.
+    pub fn vertex_indices(&self) -> bool {
+        self.vertex_indices.unwrap_or(false)
+    }
+
+    /// Returns a copy of this style without fill attributes, used for open paths.
+    pub fn without_fill(&self) -> Self {
         Self {
-            opacity: None,
             fill: None,
             fill_opacity: None,
-            stroke_color: None,
-            stroke_width: None,
-            stroke_opacity: None,
-            radius: 1.0,
+            ..self.clone()
         }
     }
 }
@@ -46,6 +116,28 @@ impl Display for Style {
         if let Some(stroke_opacity) = self.stroke_opacity {
             write!(fmt, r#" stroke-opacity="{}""#, stroke_opacity)?;
         }
+        if let Some(stroke_dasharray) = &self.stroke_dasharray {
+            write!(fmt, r#" stroke-dasharray=""#)?;
+            for (index, dash) in stroke_dasharray.iter().enumerate() {
+                if index > 0 {
+                    write!(fmt, " ")?;
+                }
+                write!(fmt, "{}", dash)?;
+            }
+            write!(fmt, r#"""#)?;
+        }
+        if let Some(stroke_linecap) = self.stroke_linecap {
+            write!(fmt, r#" stroke-linecap="{}""#, stroke_linecap)?;
+        }
+        if let Some(stroke_linejoin) = self.stroke_linejoin {
+            write!(fmt, r#" stroke-linejoin="{}""#, stroke_linejoin)?;
+        }
+        if let Some(stroke_miterlimit) = self.stroke_miterlimit {
+            write!(fmt, r#" stroke-miterlimit="{}""#, stroke_miterlimit)?;
+        }
+        if let Some(arrows) = self.arrows {
+            write!(fmt, "{}", arrows)?;
+        }
         Ok(())
     }
 }
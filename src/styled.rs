@@ -0,0 +1,115 @@
+use crate::{Style, Svg, ToSvgStr, ViewBox};
+use geo_types::Coord;
+use std::fmt::{Result, Write};
+use std::ops::Deref;
+use std::sync::{Arc, OnceLock};
+
+/// Features drawn in a single item, each one with the style returned for it and its index.
+///
+/// The returned style cascades over the one of the enclosing [`Svg`], so it only needs the
+/// fields which vary between features. `items` is a `Vec<T>`, a `&[T]` or any other pointer to
+/// a slice; an iterator of features can be collected into a `Vec`.
+///
+/// `style` is called once per feature, when the item is first rendered, and its results are
+/// reused by the following renders.
+#[derive(Clone)]
+pub struct Styled<C, F> {
+    items: C,
+    style: F,
+    styles: OnceLock<Vec<Style>>,
+}
+
+impl<C, F> Styled<C, F> {
+    pub fn new(items: C, style: F) -> Self {
+        Self {
+            items,
+            style,
+            styles: OnceLock::new(),
+        }
+    }
+
+    pub fn items(&self) -> &C {
+        &self.items
+    }
+}
+
+/// Draws a slice of features with a style computed for each of them.
+pub trait ToSvgStyled<T> {
+    /// Borrows the features into a [`Svg`] where each one is drawn with `style(feature, index)`.
+    fn to_svg_styled<'a, F>(&'a self, style: F) -> Svg<'a>
+    where
+        F: Fn(&T, usize) -> Style + Send + Sync + 'a;
+}
+
+impl<T: ToSvgStr + Sync> ToSvgStyled<T> for [T] {
+    fn to_svg_styled<'a, F>(&'a self, style: F) -> Svg<'a>
+    where
+        F: Fn(&T, usize) -> Style + Send + Sync + 'a,
+    {
+        Svg {
+            items: vec![Arc::new(Styled::new(self, style))],
+            ..Svg::default()
+        }
+    }
+}
+
+impl<T, C, F> Styled<C, F>
+where
+    C: Deref<Target = [T]>,
+    F: Fn(&T, usize) -> Style,
+{
+    /// Style returned for each feature.
+    fn feature_styles(&self) -> &[Style] {
+        self.styles.get_or_init(|| {
+            self.items
+                .iter()
+                .enumerate()
+                .map(|(index, item)| (self.style)(item, index))
+                .collect()
+        })
+    }
+}
+
+impl<T, C, F> ToSvgStr for Styled<C, F>
+where
+    T: ToSvgStr,
+    C: Deref<Target = [T]>,
+    F: Fn(&T, usize) -> Style,
+{
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
+        self.items
+            .iter()
+            .zip(self.feature_styles())
+            .try_for_each(|(item, own)| item.write_svg(out, &own.inherit(style)))
+    }
+
+    fn viewbox(&self, style: &Style) -> ViewBox {
+        self.items
+            .iter()
+            .zip(self.feature_styles())
+            .fold(ViewBox::default(), |viewbox, (item, own)| {
+                viewbox.add(&item.viewbox(&own.inherit(style)))
+            })
+    }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        self.items.iter().find_map(|item| item.label_anchor())
+    }
+
+    fn is_points(&self) -> bool {
+        self.items.is_points()
+    }
+
+    /// Styles of the features with a marker or arrows, the other ones needing no definitions.
+    fn styles(&self, style: &Style) -> Vec<Style> {
+        let mut styles = Vec::new();
+        for (item, own) in self.items.iter().zip(self.feature_styles()) {
+            let style = own.inherit(style);
+            styles.extend(item.styles(&style));
+            if own.marker.is_some() || own.arrows.is_some() {
+                styles.push(style);
+            }
+        }
+        styles
+    }
+}
@@ -1,12 +1,50 @@
-use crate::{Color, Style, ToSvgStr, ViewBox};
-use std::fmt::{Display, Formatter, Result};
+use crate::{
+    defs::{Defs, CLIP_ID},
+    precision::Num,
+    xml::Escaped,
+    Arrows, AspectRatio, Background, Color, Frame, Grid, Label, Legend, LineCap, LineJoin, Margin,
+    NorthArrow, Orientation, PointMarker, Precision, ScaleBar, Size, Style, ToSvgStr, Unit,
+    ViewBox,
+};
+use std::fmt::{self, Display, Formatter, Write};
+use std::io;
+use std::sync::Arc;
 
-#[derive(Clone)]
+/// An item of a [`Svg`], either borrowed for `'a` or owned when `'a` is `'static`.
+pub type SvgItem<'a> = Arc<dyn ToSvgStr + Send + Sync + 'a>;
+
+/// A [`Svg`] which owns all its items and can be moved across threads.
+pub type OwnedSvg = Svg<'static>;
+
+#[derive(Clone, Default)]
 pub struct Svg<'a> {
-    pub items: Vec<&'a dyn ToSvgStr>,
+    pub items: Vec<SvgItem<'a>>,
     pub siblings: Vec<Svg<'a>>,
     pub viewbox: ViewBox,
     pub style: Style,
+    pub id: Option<String>,
+    pub class: Option<String>,
+    pub label: Option<Label>,
+    pub size: Option<Size>,
+    /// ViewBox used instead of the bounds of the content, in the coordinates of the geometries.
+    pub fixed_viewbox: Option<ViewBox>,
+    pub aspect_ratio: AspectRatio,
+    /// Direction of the y axis of the whole document, only read on the root [`Svg`].
+    pub orientation: Orientation,
+    /// Whether the content outside of the viewBox is clipped.
+    pub clip: bool,
+    /// Margin added around the content of this Svg, after applying the minimum size.
+    pub margin: Option<Margin>,
+    /// Minimum width and height of the viewBox fitted to the content.
+    pub min_size: Option<(f64, f64)>,
+    pub background: Option<Background>,
+    pub frame: Option<Frame>,
+    pub grid: Option<Grid>,
+    /// Name under which this group is listed in the legend.
+    pub layer_name: Option<String>,
+    pub legend: Option<Legend>,
+    pub scale_bar: Option<ScaleBar>,
+    pub north_arrow: Option<NorthArrow>,
 }
 
 impl<'a> Svg<'a> {
@@ -17,117 +55,1031 @@ impl<'a> Svg<'a> {
 
     pub fn with_style(mut self, style: &Style) -> Self {
         self.style = style.clone();
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_style(style);
-        }
         self
     }
 
-    pub fn with_margin(mut self, margin: f32) -> Self {
-        self.viewbox = self.viewbox.with_margin(margin);
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        self.class = Some(class.into());
+        self
+    }
+
+    /// Attaches a label to each item, by default at its [`label_anchor`](ToSvgStr::label_anchor).
+    pub fn with_label(mut self, label: impl Into<Label>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Sets the `width` and `height` of the document, the content is fitted in it.
+    pub fn with_size(mut self, width: f64, height: f64, unit: Unit) -> Self {
+        self.size = Some(Size::Fixed {
+            width,
+            height,
+            unit,
+        });
+        self
+    }
+
+    /// Sets the `width` of the document, its height following the aspect ratio of the content.
+    pub fn with_width(mut self, width: f64, unit: Unit) -> Self {
+        self.size = Some(Size::Width(width, unit));
+        self
+    }
+
+    /// Sets the `height` of the document, its width following the aspect ratio of the content.
+    pub fn with_height(mut self, height: f64, unit: Unit) -> Self {
+        self.size = Some(Size::Height(height, unit));
+        self
+    }
+
+    /// Sizes the document so that one unit of the geometries measures `scale` output units.
+    pub fn with_scale(mut self, scale: f64, unit: Unit) -> Self {
+        self.size = Some(Size::Scale(scale, unit));
+        self
+    }
+
+    /// Pins the viewBox of the document instead of fitting it to the content, for instance to
+    /// render several documents with the same window.
+    ///
+    /// The viewBox is given in the coordinates of the geometries, so it is flipped with them
+    /// when the orientation is [`Orientation::YUp`].
+    pub fn with_viewbox(mut self, viewbox: impl Into<ViewBox>) -> Self {
+        self.fixed_viewbox = Some(viewbox.into());
+        self
+    }
+
+    pub fn with_aspect_ratio(mut self, aspect_ratio: AspectRatio) -> Self {
+        self.aspect_ratio = aspect_ratio;
+        self
+    }
+
+    /// Hides the content outside of the viewBox.
+    pub fn with_clip(mut self, clip: bool) -> Self {
+        self.clip = clip;
+        self
+    }
+
+    /// Fills the viewBox with `color` behind the content.
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = Some(Background::Color(color));
+        self
+    }
+
+    /// Fills the viewBox with a checkerboard of squares of `size` behind the content, to show
+    /// transparent areas.
+    pub fn with_checkerboard(mut self, size: f64) -> Self {
+        self.background = Some(Background::Checkerboard(size));
+        self
+    }
+
+    /// Draws a border of `width` on the inner edge of the viewBox.
+    pub fn with_frame(mut self, color: Color, width: f32) -> Self {
+        self.frame = Some(Frame { color, width });
+        self
+    }
+
+    /// Draws a coordinate grid over the content.
+    pub fn with_grid(mut self, grid: Grid) -> Self {
+        self.grid = Some(grid);
+        self
+    }
+
+    /// Names this group, listing it in the legend of the document with a swatch of its style.
+    pub fn with_layer_name(mut self, layer_name: impl Into<String>) -> Self {
+        self.layer_name = Some(layer_name.into());
+        self
+    }
+
+    /// Draws a legend listing the named layers of the document.
+    pub fn with_legend(mut self, legend: Legend) -> Self {
+        self.legend = Some(legend);
+        self
+    }
+
+    /// Draws a scale bar in a corner of the viewBox.
+    pub fn with_scale_bar(mut self, scale_bar: ScaleBar) -> Self {
+        self.scale_bar = Some(scale_bar);
+        self
+    }
+
+    /// Draws an arrow pointing north in a corner of the viewBox.
+    pub fn with_north_arrow(mut self, north_arrow: NorthArrow) -> Self {
+        self.north_arrow = Some(north_arrow);
+        self
+    }
+
+    pub fn with_margin(self, margin: f64) -> Self {
+        self.with_margins(margin, margin)
+    }
+
+    /// Adds `horizontal` on the left and right of the content and `vertical` on its top and
+    /// bottom.
+    pub fn with_margins(mut self, horizontal: f64, vertical: f64) -> Self {
+        self.margin = Some(Margin::Absolute(horizontal, vertical));
+        self
+    }
+
+    /// Adds a margin proportional to the size of the content, `0.1` being 10% on each side.
+    pub fn with_relative_margin(self, ratio: f64) -> Self {
+        self.with_relative_margins(ratio, ratio)
+    }
+
+    pub fn with_relative_margins(mut self, horizontal: f64, vertical: f64) -> Self {
+        self.margin = Some(Margin::Relative(horizontal, vertical));
+        self
+    }
+
+    /// Ensures the viewBox is at least `width` by `height`, so that a single point or a
+    /// straight line still gets an area.
+    pub fn with_min_size(mut self, width: f64, height: f64) -> Self {
+        self.min_size = Some((width, height));
         self
     }
 
     pub fn with_color(mut self, color: Color) -> Self {
         self.style.fill = Some(color);
         self.style.stroke_color = Some(color);
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_color(color);
-        }
         self
     }
 
+    /// Sets the opacity of the group, applied on top of the opacity of the enclosing groups.
     pub fn with_opacity(mut self, opacity: f32) -> Self {
         self.style.opacity = Some(opacity);
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_opacity(opacity);
-        }
         self
     }
 
     pub fn with_fill_color(mut self, fill: Color) -> Self {
         self.style.fill = Some(fill);
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_fill_color(fill);
-        }
         self
     }
 
     pub fn with_fill_opacity(mut self, fill_opacity: f32) -> Self {
         self.style.fill_opacity = Some(fill_opacity);
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_fill_opacity(fill_opacity);
-        }
         self
     }
 
     pub fn with_stroke_width(mut self, stroke_width: f32) -> Self {
         self.style.stroke_width = Some(stroke_width);
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_stroke_width(stroke_width);
-        }
         self
     }
 
     pub fn with_stroke_opacity(mut self, stroke_opacity: f32) -> Self {
         self.style.stroke_opacity = Some(stroke_opacity);
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_stroke_opacity(stroke_opacity);
-        }
         self
     }
 
     pub fn with_stroke_color(mut self, stroke_color: Color) -> Self {
         self.style.stroke_color = Some(stroke_color);
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_stroke_color(stroke_color);
-        }
+        self
+    }
+
+    pub fn with_stroke_dasharray(mut self, stroke_dasharray: &[f32]) -> Self {
+        self.style.stroke_dasharray = Some(stroke_dasharray.to_vec());
+        self
+    }
+
+    pub fn with_stroke_linecap(mut self, stroke_linecap: LineCap) -> Self {
+        self.style.stroke_linecap = Some(stroke_linecap);
+        self
+    }
+
+    pub fn with_stroke_linejoin(mut self, stroke_linejoin: LineJoin) -> Self {
+        self.style.stroke_linejoin = Some(stroke_linejoin);
+        self
+    }
+
+    pub fn with_stroke_miterlimit(mut self, stroke_miterlimit: f32) -> Self {
+        self.style.stroke_miterlimit = Some(stroke_miterlimit);
+        self
+    }
+
+    /// Draws arrowheads on paths to show their direction.
+    pub fn with_arrows(mut self, arrows: Arrows) -> Self {
+        self.style.arrows = Some(arrows);
         self
     }
 
     pub fn with_radius(mut self, radius: f32) -> Self {
-        self.style.radius = radius;
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_radius(radius);
-        }
+        self.style.radius = Some(radius);
+        self
+    }
+
+    pub fn with_marker(mut self, marker: PointMarker) -> Self {
+        self.style.marker = Some(marker);
+        self
+    }
+
+    /// Sets the direction of the y axis of the document.
+    ///
+    /// The orientation applies to the whole document, so it is only read on the root [`Svg`],
+    /// the one being rendered.
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn with_precision(mut self, precision: Precision) -> Self {
+        self.style.precision = Some(precision);
+        self
+    }
+
+    /// Annotates each vertex of lines and polygons with its index, marking the start and the
+    /// winding of rings, to debug geometry algorithms.
+    pub fn with_vertex_indices(mut self, vertex_indices: bool) -> Self {
+        self.style.vertex_indices = Some(vertex_indices);
         self
     }
 
     pub fn svg_str(&self) -> String {
-        self.items
-            .iter()
-            .map(|item| item.to_svg_str(&self.style))
-            .chain(self.siblings.iter().map(Svg::svg_str))
-            .collect()
+        let mut svg = String::new();
+        self.write_content(&mut svg, &self.viewbox(), &self.document_style())
+            .unwrap();
+        svg
+    }
+
+    /// Writes the whole SVG document to `out` without building it in memory.
+    pub fn write_to<W: Write>(&self, out: &mut W) -> fmt::Result {
+        let legend_entries = self.legend_entries();
+        let (viewbox, data_viewbox) = self.viewboxes(&legend_entries);
+        let precision = self
+            .style
+            .precision
+            .map(|precision| precision.resolve(&viewbox));
+        // Without precision, the viewBox keeps 15 significant digits to drop the rounding errors
+        // of the bounds computation.
+        let number = |value: f64| Num(value, precision.or(Some(Precision::Significant(15))));
+        write!(out, r#"<svg xmlns="http://www.w3.org/2000/svg""#)?;
+        if let Some(size) = self.size {
+            let (width, height, unit) = size.resolve(&viewbox);
+            let size_precision = Some(Precision::Decimals(3));
+            write!(
+                out,
+                r#" width="{}{unit}" height="{}{unit}""#,
+                Num(width, size_precision),
+                Num(height, size_precision),
+                unit = unit,
+            )?;
+        }
+        write!(
+            out,
+            r#" preserveAspectRatio="{aspect_ratio}" viewBox="{x} {y} {w} {h}">"#,
+            aspect_ratio = self.aspect_ratio,
+            x = number(viewbox.min_x()),
+            y = number(viewbox.min_y()),
+            w = number(viewbox.width()),
+            h = number(viewbox.height()),
+        )?;
+        let mut defs = Defs::default();
+        let mut styles = Vec::new();
+        self.collect_styles(&mut styles, &Style::default());
+        for style in &styles {
+            defs.add_style(style);
+        }
+        if self.clip {
+            defs.add_clip(viewbox);
+        }
+        if let Some(Background::Checkerboard(size)) = self.background {
+            defs.add_checkerboard(size);
+        }
+        if let Some(legend) = &self.legend {
+            for (_, style) in &legend_entries {
+                defs.add_style(&legend.swatch_style(style, &data_viewbox));
+            }
+        }
+        defs.write(out, precision)?;
+        if let Some(background) = &self.background {
+            background.write(out, &viewbox, precision)?;
+        }
+        if let Some(frame) = &self.frame {
+            frame.write(out, &viewbox, precision)?;
+        }
+        if self.clip {
+            write!(out, r#"<g clip-path="url(#{})">"#, CLIP_ID)?;
+        }
+        self.write_content(out, &viewbox, &self.document_style())?;
+        if self.clip {
+            write!(out, "</g>")?;
+        }
+        if let Some(grid) = &self.grid {
+            grid.write(out, &viewbox, self.orientation, precision)?;
+        }
+        if let Some(legend) = &self.legend {
+            legend.write(
+                out,
+                &legend_entries,
+                &data_viewbox,
+                self.fixed_viewbox.is_some(),
+                precision,
+            )?;
+        }
+        if let Some(scale_bar) = &self.scale_bar {
+            scale_bar.write(out, &viewbox, precision)?;
+        }
+        if let Some(north_arrow) = &self.north_arrow {
+            north_arrow.write(out, &viewbox, self.orientation, precision)?;
+        }
+        write!(out, "</svg>")
+    }
+
+    /// Named layers with their effective style, followed by the entries added to the legend.
+    fn legend_entries(&self) -> Vec<(String, Style)> {
+        let mut entries = Vec::new();
+        if let Some(legend) = &self.legend {
+            self.collect_layers(&mut entries, &Style::default());
+            entries.extend(legend.entries.iter().cloned());
+        }
+        entries
+    }
+
+    fn collect_layers(&self, entries: &mut Vec<(String, Style)>, parent: &Style) {
+        let style = self.style.inherit(parent);
+        if let Some(layer_name) = &self.layer_name {
+            let mut entry = style.clone();
+            if entry.marker.is_none() && self.holds_points() {
+                entry.marker = Some(PointMarker::Circle);
+            }
+            entries.push((layer_name.clone(), entry));
+        }
+        for sibling in &self.siblings {
+            sibling.collect_layers(entries, &style);
+        }
+    }
+
+    /// Whether this Svg and its siblings only hold points.
+    pub(crate) fn holds_points(&self) -> bool {
+        (!self.items.is_empty() || !self.siblings.is_empty())
+            && self.items.iter().all(|item| item.is_points())
+            && self.siblings.iter().all(Svg::holds_points)
+    }
+
+    /// Collects the effective styles of the groups holding items, and of the parts of the items
+    /// drawn with their own style.
+    pub(crate) fn collect_styles(&self, styles: &mut Vec<Style>, parent: &Style) {
+        let style = self.style.inherit(parent);
+        if !self.items.is_empty() {
+            for item in &self.items {
+                styles.extend(item.styles(&style));
+            }
+            styles.push(style.clone());
+        }
+        for sibling in &self.siblings {
+            sibling.collect_styles(styles, &style);
+        }
     }
 
+    /// Writes the whole SVG document to an [`io::Write`], such as a buffered file.
+    pub fn write_to_io<W: io::Write>(&self, out: W) -> io::Result<()> {
+        let mut writer = IoWriter {
+            inner: out,
+            error: None,
+        };
+        match self.write_to(&mut writer) {
+            Ok(()) => Ok(()),
+            Err(fmt::Error) => Err(writer
+                .error
+                .unwrap_or_else(|| io::Error::other("formatter error"))),
+        }
+    }
+
+    /// Writes a `<g>` element holding this style, with the items and siblings inside it.
+    ///
+    /// The `parent` style is the effective style of the enclosing group. The items only get its
+    /// rendering parameters cascaded with this style, their attributes are inherited from the
+    /// enclosing groups. Symbol markers also get the stroke width, to scale it with the radius.
+    pub(crate) fn write_content(
+        &self,
+        out: &mut dyn Write,
+        viewbox: &ViewBox,
+        parent: &Style,
+    ) -> fmt::Result {
+        let effective = self.style.inherit(parent);
+        let mut style = effective.without_attributes();
+        style.precision = style.precision.map(|precision| precision.resolve(viewbox));
+        if let Some(marker) = &effective.marker {
+            if marker.is_symbol() {
+                style.stroke_width = effective.stroke_width;
+            }
+        }
+        write!(out, "<g")?;
+        if let Some(id) = &self.id {
+            write!(out, r#" id="{}""#, Escaped(id))?;
+        }
+        if let Some(class) = &self.class {
+            write!(out, r#" class="{}""#, Escaped(class))?;
+        }
+        write!(out, "{}", self.style)?;
+        if let Some(marker) = &effective.marker {
+            if marker.is_stroke_only() {
+                if let Some(color) = effective.stroke_color.or(effective.fill) {
+                    write!(out, r#" color="{}""#, color)?;
+                }
+            }
+        }
+        write!(out, ">")?;
+        for item in &self.items {
+            item.write_svg(out, &style)?;
+        }
+        if let Some(label) = &self.label {
+            for item in &self.items {
+                label.write(out, item.label_anchor(), &style)?;
+            }
+        }
+        for sibling in &self.siblings {
+            sibling.write_content(out, viewbox, &effective)?;
+        }
+        write!(out, "</g>")
+    }
+
+    /// ViewBox of the document, either the fixed one or the bounds of the content and legend.
     pub fn viewbox(&self) -> ViewBox {
-        self.items
+        self.viewboxes(&self.legend_entries()).0
+    }
+
+    /// ViewBox of the document and the one of the data, next to which the legend is placed.
+    fn viewboxes(&self, legend_entries: &[(String, Style)]) -> (ViewBox, ViewBox) {
+        if let Some(viewbox) = self.fixed_viewbox {
+            let viewbox = self.orientation.apply_viewbox(viewbox);
+            return (viewbox, viewbox);
+        }
+        let data_viewbox = self.data_viewbox();
+        let viewbox = match &self.legend {
+            Some(legend) => data_viewbox.add(&legend.bounds(legend_entries, &data_viewbox, false)),
+            None => data_viewbox,
+        };
+        (viewbox, data_viewbox)
+    }
+
+    /// Bounds of the content with the minimum size and margin applied.
+    fn data_viewbox(&self) -> ViewBox {
+        self.viewbox_in(&self.document_style())
+    }
+
+    /// Style holding the document-level parameters, cascaded under the style of the root.
+    fn document_style(&self) -> Style {
+        Style {
+            orientation: Some(self.orientation),
+            ..Style::default()
+        }
+    }
+
+    /// Computes the viewbox, with this style cascading over the `parent` one, and the minimum
+    /// size and margin of this Svg applied.
+    pub(crate) fn viewbox_in(&self, parent: &Style) -> ViewBox {
+        let style = self.style.inherit(parent);
+        let labels = self.label.iter().flat_map(|label| {
+            let style = &style;
+            self.items
+                .iter()
+                .map(move |item| label.viewbox(item.label_anchor(), style))
+        });
+        let mut viewbox = self
+            .items
             .iter()
-            .map(|item| item.viewbox(&self.style))
-            .chain(self.siblings.iter().map(Svg::viewbox))
+            .map(|item| item.viewbox(&style))
+            .chain(labels)
+            .chain(
+                self.siblings
+                    .iter()
+                    .map(|sibling| sibling.viewbox_in(&style)),
+            )
             .fold(self.viewbox, |viewbox, other_viewbox| {
                 viewbox.add(&other_viewbox)
-            })
+            });
+        if let Some((width, height)) = self.min_size {
+            viewbox = viewbox.with_min_size(width, height);
+        }
+        match self.margin {
+            Some(margin) => margin.apply(viewbox),
+            None => viewbox,
+        }
     }
 }
 
-impl<'a> Display for Svg<'a> {
-    fn fmt(&self, fmt: &mut Formatter) -> Result {
-        let viewbox = self.viewbox();
-        write!(
-            fmt,
-            r#"<svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet" viewBox="{x} {y} {w} {h}">{content}</svg>"#,
-            x = viewbox.min_x(),
-            y = viewbox.min_y(),
-            w = viewbox.width(),
-            h = viewbox.height(),
-            content = self
-                .items
-                .iter()
-                .map(|item| item.to_svg_str(&self.style))
-                .chain(self.siblings.iter().map(Svg::svg_str))
-                .collect::<String>()
-        )
+impl Display for Svg<'_> {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        self.write_to(fmt)
+    }
+}
+
+/// Adapts an [`io::Write`] to [`fmt::Write`], keeping the underlying I/O error.
+struct IoWriter<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Align, Arrows, AspectRatio, Color, DistanceUnit, Grid, Label, Legend, LineCap, LineJoin,
+        NorthArrow, Orientation, OwnedSvg, PointMarker, Precision, ScaleBar, Style, Styled, ToSvg,
+        ToSvgStyled, Unit, ViewBox,
+    };
+    use geo_types::{Line, LineString, MultiPoint, Point, Polygon, Rect};
+
+    fn scene() -> OwnedSvg {
+        let point = Point::new(10.0, 28.1);
+        let line = Line::new((114.19, 22.26), (15.93, -15.76));
+        point
+            .into_svg()
+            .and(line.into_svg())
+            .with_stroke_color(Color::named("black").unwrap())
+    }
+
+    #[test]
+    fn test_owned_svg() {
+        let svg = scene();
+        let expected = svg.to_string();
+        let rendered = std::thread::spawn(move || svg.to_string()).join().unwrap();
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn test_owned_and_borrowed() {
+        let point = Point::new(0.0, 0.0);
+        let svg = scene().and(point.to_svg());
+        assert_eq!(svg.to_string().matches("<circle").count(), 2);
+    }
+
+    #[test]
+    fn test_orientation() {
+        let line_string = LineString::from(vec![(0.0, 0.0), (10.0, 20.0)]);
+        let svg = line_string
+            .to_svg()
+            .and(Point::new(10.0, 20.0).into_svg())
+            .with_orientation(Orientation::YUp);
+        let viewbox = svg.viewbox();
+        assert_eq!(viewbox.min_y(), -22.0);
+        assert_eq!(viewbox.max_y(), 1.0);
+        let rendered = svg.to_string();
+        assert!(rendered.contains(r#"d="M 0.0 0.0 L 10.0 -20.0""#));
+        assert!(rendered.contains(r#"<circle cx="10.0" cy="-20.0" r="1""#));
+    }
+
+    #[test]
+    fn test_orientation_of_nested_svg_ignored() {
+        let rendered = Point::new(1.0, 2.0)
+            .to_svg()
+            .and(
+                Point::new(3.0, 4.0)
+                    .into_svg()
+                    .with_orientation(Orientation::YUp),
+            )
+            .to_string();
+        assert!(rendered.contains(r#"<circle cx="3.0" cy="4.0" r="1"/>"#));
+
+        let rendered = Point::new(1.0, 2.0)
+            .to_svg()
+            .and(
+                Point::new(3.0, 4.0)
+                    .into_svg()
+                    .with_orientation(Orientation::YDown),
+            )
+            .with_orientation(Orientation::YUp)
+            .to_string();
+        assert!(rendered.contains(r#"<circle cx="3.0" cy="-4.0" r="1"/>"#));
+    }
+
+    #[test]
+    fn test_orientation_unsigned() {
+        let rendered = Point::new(3u32, 4u32)
+            .to_svg()
+            .and(LineString::from(vec![(0u32, 0u32), (3, 4)]).into_svg())
+            .with_orientation(Orientation::YUp)
+            .to_string();
+        assert!(rendered.contains(r#"<circle cx="3.0" cy="-4.0" r="1""#));
+        assert!(rendered.contains(r#"d="M 0.0 0.0 L 3.0 -4.0""#));
+    }
+
+    #[test]
+    fn test_projected_viewbox() {
+        let point = Point::new(652_345.125, 5_412_871.625);
+        let viewbox = point.to_svg().with_radius(0.25).viewbox();
+        assert_eq!(viewbox.min_x(), 652_343.875);
+        assert_eq!(viewbox.max_y(), 5_412_872.875);
+
+        let rendered = Point::new(0.0, 0.0).to_svg().with_radius(0.1).to_string();
+        assert!(rendered.contains(r#"viewBox="-1.1 -1.1 2.2 2.2""#));
+    }
+
+    #[test]
+    fn test_precision() {
+        let line = Line::new((0.1 + 0.2, 1.0 / 3.0), (10.0, 20.0));
+        let svg = line.to_svg().with_precision(Precision::Decimals(2));
+        assert_eq!(
+            svg.to_string(),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet" viewBox="-0.7 -0.67 11.7 21.67"><g><path d="M 0.3 0.33 L 10 20"/></g></svg>"#
+        );
+    }
+
+    #[test]
+    fn test_write_to_io() {
+        let svg = scene();
+        let mut buffer = Vec::new();
+        svg.write_to_io(&mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), svg.to_string());
+
+        let mut full = [0u8; 16];
+        let error = svg.write_to_io(&mut full[..]).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn test_style_cascade() {
+        let svg = Point::new(0.0, 0.0)
+            .into_svg()
+            .with_stroke_color(Color::named("black").unwrap())
+            .and(
+                Point::new(50.0, 0.0)
+                    .into_svg()
+                    .with_radius(5.0)
+                    .with_stroke_color(Color::named("blue").unwrap()),
+            )
+            .with_radius(10.0)
+            .with_stroke_color(Color::named("red").unwrap())
+            .with_fill_color(Color::named("green").unwrap());
+        assert_eq!(
+            svg.svg_str(),
+            concat!(
+                r#"<g fill="green" stroke="red"><circle cx="0.0" cy="0.0" r="10"/>"#,
+                r#"<g stroke="blue"><circle cx="50.0" cy="0.0" r="5"/></g></g>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_group_id_and_class() {
+        let svg = scene().with_id("scene").with_class(r#"debug "layer""#);
+        assert!(svg
+            .svg_str()
+            .starts_with(r#"<g id="scene" class="debug &quot;layer&quot;" stroke="black">"#));
+    }
+
+    #[test]
+    fn test_stroke_style() {
+        let line_string = LineString::from(vec![(0.0, 0.0), (10.0, 20.0)]);
+        let svg = line_string
+            .to_svg()
+            .with_stroke_dasharray(&[4.0, 2.5])
+            .with_stroke_linecap(LineCap::Round)
+            .with_stroke_linejoin(LineJoin::Bevel)
+            .with_stroke_miterlimit(2.0);
+        assert!(svg.svg_str().starts_with(
+            r#"<g stroke-dasharray="4 2.5" stroke-linecap="round" stroke-linejoin="bevel" stroke-miterlimit="2">"#
+        ));
+    }
+
+    #[test]
+    fn test_point_markers() {
+        let points = MultiPoint::from(vec![(0.0, 0.0), (10.0, 0.0)]);
+        let svg = points
+            .to_svg()
+            .with_marker(PointMarker::Diamond)
+            .with_radius(2.0)
+            .with_stroke_width(0.5)
+            .and(
+                Point::new(5.0, 5.0)
+                    .into_svg()
+                    .with_marker(PointMarker::Square),
+            )
+            .with_precision(Precision::Decimals(2));
+        let rendered = svg.to_string();
+        assert_eq!(rendered.matches("<symbol id=").count(), 1);
+        assert!(rendered.contains(
+            r#"<defs><symbol id="geo-svg-diamond" viewBox="-1 -1 2 2" overflow="visible"><path d="M 0 -1 L 1 0 L 0 1 L -1 0 Z"/></symbol></defs>"#
+        ));
+        assert!(rendered.contains(
+            r##"<g stroke-width="0.5"><use href="#geo-svg-diamond" x="-2" y="-2" width="4" height="4" stroke-width="0.25"/>"##
+        ));
+        assert!(rendered.contains(r#"<rect x="3" y="3" width="4" height="4"/>"#));
+    }
+
+    #[test]
+    fn test_stroke_only_marker_with_fill() {
+        let rendered = Point::new(0.0, 0.0)
+            .to_svg()
+            .with_fill_color(Color::named("red").unwrap())
+            .with_marker(PointMarker::Cross)
+            .with_radius(2.0)
+            .to_string();
+        assert!(rendered.contains(
+            r#"<path d="M -1 0 L 1 0 M 0 -1 L 0 1" fill="none" stroke="currentColor"/>"#
+        ));
+        assert!(rendered.contains(
+            r##"<g fill="red" color="red"><use href="#geo-svg-cross" x="-2.0" y="-2.0" width="4.0" height="4.0" stroke-width="0.5"/>"##
+        ));
+    }
+
+    #[test]
+    fn test_symbol_defined_once() {
+        let symbol = PointMarker::Symbol {
+            id: "s".to_string(),
+            content: r#"<circle r="1"/>"#.to_string(),
+        };
+        let rendered = OwnedSvg::default()
+            .and(
+                Point::new(0.0, 0.0)
+                    .into_svg()
+                    .with_marker(symbol.clone())
+                    .with_radius(1.0),
+            )
+            .and(
+                Point::new(5.0, 0.0)
+                    .into_svg()
+                    .with_marker(symbol)
+                    .with_radius(2.0),
+            )
+            .to_string();
+        assert_eq!(rendered.matches(r#"<symbol id="s""#).count(), 1);
+        assert_eq!(rendered.matches(r##"<use href="#s""##).count(), 2);
+    }
+
+    #[test]
+    fn test_label() {
+        let line = Line::new((0.0, 0.0), (20.0, 0.0));
+        let svg = line
+            .to_svg()
+            .with_label(
+                Label::new("a < b")
+                    .with_font_size(4.0)
+                    .with_offset(0.0, 3.0)
+                    .with_halo(Color::named("white").unwrap(), 1.0),
+            )
+            .with_orientation(Orientation::YUp);
+        assert!(svg.svg_str().contains(
+            r#"<text x="10.0" y="-3.0" font-size="4" text-anchor="middle" dominant-baseline="central" fill="black" stroke="white" stroke-width="1" stroke-opacity="1" stroke-dasharray="none" stroke-linejoin="round" paint-order="stroke">a &lt; b</text>"#
+        ));
+        let viewbox = svg.viewbox();
+        assert_eq!(viewbox.min_y(), -6.0);
+        assert_eq!(viewbox.min_x(), -1.0);
+    }
+
+    #[test]
+    fn test_vertex_indices() {
+        let polygon = Polygon::new(
+            LineString::from(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]),
+            vec![],
+        );
+        let svg = polygon.to_svg().with_vertex_indices(true);
+        let rendered = svg.svg_str();
+        assert_eq!(rendered.matches("<text").count(), 4);
+        assert!(rendered.contains(">0 cw</text>"));
+        assert!(rendered.contains(">3</text>"));
+        assert!(svg.viewbox().max_x() > 11.0);
+
+        let flipped = svg.with_orientation(Orientation::YUp).svg_str();
+        assert!(flipped.contains(">0 ccw</text>"));
+    }
+
+    #[test]
+    fn test_arrows() {
+        let line_string = LineString::from(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+        let rendered = line_string
+            .to_svg()
+            .with_arrows(Arrows::end())
+            .with_stroke_width(0.5)
+            .to_string();
+        assert_eq!(rendered.matches(r#"<marker id="geo-svg-arrow""#).count(), 1);
+        assert!(rendered.contains(r#"fill="black" stroke="none" style="fill:context-stroke"/>"#));
+        assert!(rendered.contains(
+            r##"<g stroke-width="0.5" marker-start="none" marker-mid="none" marker-end="url(#geo-svg-arrow)">"##
+        ));
+
+        let rendered = Line::new((0.0, 0.0), (10.0, 0.0))
+            .to_svg()
+            .with_arrows(Arrows::all())
+            .to_string();
+        assert!(rendered.contains(
+            r##"<g marker-start="none" marker-mid="url(#geo-svg-arrow)" marker-end="url(#geo-svg-arrow)"><path d="M 0.0 0.0 L 10.0 0.0"/></g>"##
+        ));
+    }
+
+    #[test]
+    fn test_size() {
+        let line = Line::new((0.0, 0.0), (98.0, 48.0));
+        let scaled = line.to_svg().with_scale(2.0, Unit::Mm).to_string();
+        assert!(scaled.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="200mm" height="100mm""#
+        ));
+        let width = line.to_svg().with_width(50.0, Unit::Px).to_string();
+        assert!(width.contains(r#" width="50px" height="25px""#));
+    }
+
+    #[test]
+    fn test_fixed_viewbox() {
+        let line = Line::new((0.0, 0.0), (98.0, 48.0));
+        let rendered = line
+            .to_svg()
+            .with_viewbox(Rect::new((0.0, 0.0), (50.0, 25.0)))
+            .with_aspect_ratio(AspectRatio::Slice(Align::Min, Align::Max))
+            .with_clip(true)
+            .to_string();
+        assert!(rendered.contains(r#" preserveAspectRatio="xMinYMax slice" viewBox="0 0 50 25">"#));
+        assert!(rendered.contains(
+            r#"<defs><clipPath id="geo-svg-clip"><rect x="0.0" y="0.0" width="50.0" height="25.0"/></clipPath></defs><g clip-path="url(#geo-svg-clip)">"#
+        ));
+    }
+
+    #[test]
+    fn test_nested_opacity() {
+        let point = Point::new(0.0, 0.0);
+        let svg = OwnedSvg::default()
+            .and(point.into_svg().with_opacity(0.8).with_layer_name("Cities"))
+            .with_opacity(0.5)
+            .with_legend(Legend::new());
+        let rendered = svg.to_string();
+        assert!(rendered.contains(r#"<g opacity="0.5"><g opacity="0.8"><circle"#));
+        assert!(rendered.contains(r#"opacity="0.4"/>"#));
+    }
+
+    #[test]
+    fn test_margins() {
+        let point = Point::new(10.0, 10.0);
+        let svg = point
+            .to_svg()
+            .with_radius(0.0)
+            .with_stroke_width(0.0)
+            .with_min_size(4.0, 2.0)
+            .with_relative_margin(0.5);
+        assert_eq!(svg.viewbox(), ViewBox::new(6.0, 8.0, 14.0, 12.0));
+        let svg = point.to_svg().with_margin(1.0);
+        assert_eq!(svg.viewbox(), ViewBox::new(7.0, 7.0, 13.0, 13.0));
+
+        let sibling = Point::new(15.0, 10.0);
+        let svg = point.to_svg().and(sibling.to_svg().with_margin(100.0));
+        assert_eq!(svg.viewbox(), ViewBox::new(-87.0, -92.0, 117.0, 112.0));
+    }
+
+    #[test]
+    fn test_background_and_frame() {
+        let line = Line::new((0.0, 0.0), (10.0, 10.0));
+        let svg = line
+            .to_svg()
+            .with_viewbox(ViewBox::new(0.0, 0.0, 10.0, 10.0))
+            .with_precision(Precision::Decimals(1))
+            .with_frame(Color::named("gray").unwrap(), 1.0);
+        let rendered = svg
+            .clone()
+            .with_background(Color::named("white").unwrap())
+            .to_string();
+        assert!(rendered.contains(
+            r#"viewBox="0 0 10 10"><rect x="0" y="0" width="10" height="10" stroke="none" fill="white"/><rect x="0.5" y="0.5" width="9" height="9" fill="none" stroke="gray" stroke-width="1"/><g>"#
+        ));
+        let rendered = svg.with_checkerboard(1.0).to_string();
+        assert!(rendered.contains(r#"<pattern id="geo-svg-checkerboard" width="2" height="2""#));
+        assert!(rendered.contains(r##"fill="url(#geo-svg-checkerboard)"/>"##));
+    }
+
+    #[test]
+    fn test_grid() {
+        let line = Line::new((0.0, 0.0), (10.0, 10.0));
+        let rendered = line
+            .to_svg()
+            .with_viewbox(ViewBox::new(-1.0, -1.0, 11.0, 11.0))
+            .with_orientation(Orientation::YUp)
+            .with_grid(Grid::new().with_spacing(5.0))
+            .to_string();
+        assert!(
+            rendered.contains(r#"viewBox="-1 -11 12 12"><g><path d="M 0.0 0.0 L 10.0 -10.0"/>"#)
+        );
+        assert!(rendered.contains(
+            r#"<g class="geo-svg-grid"><path d="M 0.0 -11.0 V 1.0 M 5.0 -11.0 V 1.0 M 10.0 -11.0 V 1.0 M -1.0 -10.0 H 11.0 M -1.0 -5.0 H 11.0 M -1.0 0.0 H 11.0" fill="none" stroke="gray" stroke-width="0.5" vector-effect="non-scaling-stroke"/>"#
+        ));
+        assert!(rendered.contains(r#"<text x="-1.0" y="-10.0" dx="0.075" dy="-0.075">10</text>"#));
+    }
+
+    #[test]
+    fn test_legend() {
+        let point = Point::new(0.0, 0.0);
+        let line = Line::new((0.0, 0.0), (40.0, 20.0));
+        let svg = OwnedSvg::default()
+            .and(
+                point
+                    .into_svg()
+                    .with_fill_color(Color::named("red").unwrap())
+                    .with_marker(PointMarker::Triangle)
+                    .with_layer_name("Cities"),
+            )
+            .and(
+                line.into_svg()
+                    .with_stroke_color(Color::named("blue").unwrap())
+                    .with_layer_name("Roads"),
+            )
+            .with_legend(Legend::new().with_font_size(2.0).with_entry(
+                "Lakes & rivers",
+                Style {
+                    fill: Some(Color::named("cyan").unwrap()),
+                    ..Style::default()
+                },
+            ));
+        assert_eq!(svg.viewbox(), ViewBox::new(-2.0, -2.0, 65.8, 21.0));
+        let rendered = svg.to_string();
+        assert!(rendered.contains(r#"<symbol id="geo-svg-triangle""#));
+        assert!(rendered.contains(r#"<g class="geo-svg-legend">"#));
+        assert!(rendered
+            .contains(r#"width="1.3333334" height="1.3333334" fill="red" stroke-width="1.5"/>"#));
+        assert!(rendered.contains(r#"<path d="M 44.0 3.5 H 46.0" fill="none" stroke="blue"/>"#));
+        assert!(rendered.contains(r#"fill="cyan"/>"#));
+        assert!(rendered.contains(">Lakes &amp; rivers</text>"));
+        assert!(rendered.ends_with("</text></g></svg>"));
+    }
+
+    #[test]
+    fn test_legend_point_layer() {
+        let points = MultiPoint::from(vec![(0.0, 0.0), (10.0, 10.0)]);
+        let rendered = OwnedSvg::default()
+            .and(
+                points
+                    .into_svg()
+                    .with_fill_color(Color::named("red").unwrap())
+                    .with_layer_name("Wells"),
+            )
+            .with_legend(Legend::new().with_font_size(3.0))
+            .to_string();
+        assert!(rendered.contains(r#"<circle cx="18.0" cy="1.75" r="1" fill="red"/>"#));
+    }
+
+    #[test]
+    fn test_scale_bar_and_north_arrow() {
+        let line = Line::new((0.0, 0.0), (4000.0, 2000.0));
+        let rendered = line
+            .to_svg()
+            .with_viewbox(ViewBox::new(0.0, 0.0, 4000.0, 2000.0))
+            .with_orientation(Orientation::YUp)
+            .with_scale_bar(ScaleBar::new(DistanceUnit::Meters))
+            .with_north_arrow(NorthArrow::new().with_size(300.0))
+            .to_string();
+        assert!(rendered
+            .contains(r#"viewBox="0 -2000 4000 2000"><g><path d="M 0.0 0.0 L 4000.0 -2000.0"/>"#));
+        assert!(rendered.contains(
+            r#"<g class="geo-svg-scale-bar" stroke="black" stroke-width="2.5"><rect x="50.0" y="-75.0" width="500.0" height="25.0" fill="black"/>"#
+        ));
+        assert!(rendered.contains(r#"text-anchor="end">1 km</text>"#));
+        assert!(rendered.contains(
+            r#"<g class="geo-svg-north-arrow" fill="black" stroke="none"><path d="M 3750.0 -1775.0 L 3840.0 -1475.0 L 3750.0 -1550.0 L 3660.0 -1475.0 Z"/>"#
+        ));
+    }
+
+    #[test]
+    fn test_styled() {
+        let parcels = [
+            Rect::new((0.0, 0.0), (1.0, 1.0)),
+            Rect::new((1.0, 0.0), (2.0, 1.0)),
+        ];
+        let rendered = parcels
+            .to_svg_styled(|_, index| Style {
+                fill: Some(if index == 0 {
+                    Color::named("green").unwrap()
+                } else {
+                    Color::named("yellow").unwrap()
+                }),
+                ..Style::default()
+            })
+            .with_stroke_color(Color::named("black").unwrap())
+            .to_string();
+        assert!(rendered.contains(r#"<g stroke="black"><path fill-rule="evenodd" d="M 0.0 0.0 L 1.0 0.0 L 1.0 1.0 L 0.0 1.0 L 0.0 0.0 Z " fill="green"/><path"#));
+        assert!(rendered.contains(r#"fill="yellow"/></g>"#));
+
+        let cities = (0..3).map(|x| Point::new(x as f64, 0.0));
+        let rendered = Styled::new(cities.collect::<Vec<_>>(), |city: &Point, _| Style {
+            marker: Some(PointMarker::Star),
+            radius: Some(city.x() as f32 + 1.0),
+            ..Style::default()
+        })
+        .into_svg()
+        .to_string();
+        assert_eq!(rendered.matches(r#"<symbol id="geo-svg-star""#).count(), 1);
+        assert!(rendered.contains(
+            r##"<use href="#geo-svg-star" x="-1.0" y="-3.0" width="6.0" height="6.0" stroke-width="0.33333334"/>"##
+        ));
+    }
+
+    #[test]
+    fn test_styled_calls() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = AtomicUsize::new(0);
+        let points = (0..10)
+            .map(|x| Point::new(x as f64, 0.0))
+            .collect::<Vec<_>>();
+        let svg = points.to_svg_styled(|_, _| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            Style::default()
+        });
+        svg.to_string();
+        svg.to_string();
+        assert_eq!(calls.load(Ordering::Relaxed), 10);
     }
 }
@@ -1,93 +1,219 @@
-use crate::{Style, ToSvgStr, ViewBox};
+use crate::{
+    label::{line_string_midpoint, polygon_interior_point, to_f64},
+    precision::{widen, Num},
+    vertices::{vertex_indices_viewbox, write_vertex_indices},
+    PointMarker, Style, ToSvgStr, ViewBox,
+};
 use geo_types::{
-    CoordNum, Coordinate, Geometry, GeometryCollection, Line, LineString, MultiLineString,
-    MultiPoint, MultiPolygon, Point, Polygon, Rect, Triangle,
+    Coord, CoordNum, Geometry, GeometryCollection, Line, LineString, MultiLineString, MultiPoint,
+    MultiPolygon, Point, Polygon, Rect, Triangle,
 };
 use num_traits::NumCast;
+use std::fmt::{Result, Write};
+
+/// Unsigned area of a ring, used to pick the largest polygon.
+fn ring_area<T: CoordNum>(ring: &LineString<T>) -> f64 {
+    ring.lines()
+        .map(|line| {
+            let (start, end) = (to_f64(line.start), to_f64(line.end));
+            start.x * end.y - end.x * start.y
+        })
+        .sum::<f64>()
+        .abs()
+        / 2.0
+}
 
-impl<T: CoordNum> ToSvgStr for Coordinate<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        Point::from(*self).to_svg_str(style)
+/// Bounds of line segments, which are only widened by the stroke.
+fn segments_viewbox<T: CoordNum>(lines: impl Iterator<Item = Line<T>>, style: &Style) -> ViewBox {
+    let style = Style {
+        radius: Some(0.0),
+        ..style.clone()
+    };
+    lines.fold(ViewBox::default(), |view_box, line| {
+        view_box
+            .add(&line.start.viewbox(&style))
+            .add(&line.end.viewbox(&style))
+    })
+}
+
+/// Writes the `M x y L x y ...` commands of a subpath, skipping line strings with fewer than two points.
+fn write_subpath<T: CoordNum>(
+    out: &mut dyn Write,
+    line_string: &LineString<T>,
+    style: &Style,
+) -> Result {
+    if line_string.0.len() < 2 {
+        return Ok(());
+    }
+    let mut coords = line_string
+        .0
+        .iter()
+        .map(|coord| style.orientation().apply(*coord));
+    if let Some(first) = coords.next() {
+        write!(
+            out,
+            "M {} {}",
+            Num(first.x, style.precision),
+            Num(first.y, style.precision)
+        )?;
+    }
+    for coord in coords {
+        write!(
+            out,
+            " L {} {}",
+            Num(coord.x, style.precision),
+            Num(coord.y, style.precision)
+        )?;
+    }
+    Ok(())
+}
+
+impl<T: CoordNum> ToSvgStr for Coord<T> {
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
+        Point::from(*self).write_svg(out, style)
     }
 
     fn viewbox(&self, style: &Style) -> ViewBox {
         Point::from(*self).viewbox(style)
     }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        Some(to_f64(*self))
+    }
+
+    fn is_points(&self) -> bool {
+        true
+    }
 }
 
 impl<T: CoordNum> ToSvgStr for Point<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        format!(
-            r#"<circle cx="{x:?}" cy="{y:?}" r="{radius}"{style}/>"#,
-            x = self.x(),
-            y = self.y(),
-            radius = style.radius,
-            style = style,
-        )
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
+        let coord = style.orientation().apply(self.0);
+        style
+            .marker
+            .as_ref()
+            .unwrap_or(&PointMarker::Circle)
+            .write(out, coord, style)
     }
 
     fn viewbox(&self, style: &Style) -> ViewBox {
-        let radius = style.radius + style.stroke_width.unwrap_or(1.0);
-        ViewBox::new(
-            NumCast::from(self.x()).unwrap_or(0f32) - radius,
-            NumCast::from(self.y()).unwrap_or(0f32) - radius,
-            NumCast::from(self.x()).unwrap_or(0f32) + radius,
-            NumCast::from(self.y()).unwrap_or(0f32) + radius,
-        )
+        let radius = widen(style.radius()) + widen(style.stroke_width.unwrap_or(1.0));
+        let coord = style.orientation().apply(self.0);
+        let x: f64 = NumCast::from(coord.x).unwrap_or(0.0);
+        let y: f64 = NumCast::from(coord.y).unwrap_or(0.0);
+        ViewBox::new(x - radius, y - radius, x + radius, y + radius)
+    }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        Some(to_f64(self.0))
+    }
+
+    fn is_points(&self) -> bool {
+        true
     }
 }
 
 impl<T: CoordNum> ToSvgStr for MultiPoint<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        self.0.iter().map(|point| point.to_svg_str(style)).collect()
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
+        self.0
+            .iter()
+            .try_for_each(|point| point.write_svg(out, style))?;
+        let coords = self.0.iter().map(|point| point.0).collect::<Vec<_>>();
+        write_vertex_indices(out, &coords, false, style)
     }
 
     fn viewbox(&self, style: &Style) -> ViewBox {
-        self.0.iter().fold(ViewBox::default(), |view_box, point| {
-            view_box.add(&point.viewbox(style))
-        })
+        let coords = self.0.iter().map(|point| point.0).collect::<Vec<_>>();
+        self.0.iter().fold(
+            vertex_indices_viewbox(&coords, false, style),
+            |view_box, point| view_box.add(&point.viewbox(style)),
+        )
+    }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        self.0.first().map(|point| to_f64(point.0))
+    }
+
+    fn is_points(&self) -> bool {
+        true
     }
 }
 
 impl<T: CoordNum> ToSvgStr for Line<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        format!(
-            r#"<path d="M {x1:?} {y1:?} L {x2:?} {y2:?}"{style}/>"#,
-            x1 = self.start.x,
-            y1 = self.start.y,
-            x2 = self.end.x,
-            y2 = self.end.y,
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
+        let start = style.orientation().apply(self.start);
+        let end = style.orientation().apply(self.end);
+        write!(
+            out,
+            r#"<path d="M {x1} {y1} L {x2} {y2}"{style}/>"#,
+            x1 = Num(start.x, style.precision),
+            y1 = Num(start.y, style.precision),
+            x2 = Num(end.x, style.precision),
+            y2 = Num(end.y, style.precision),
             style = style,
-        )
+        )?;
+        write_vertex_indices(out, &[self.start, self.end], false, style)
     }
 
     fn viewbox(&self, style: &Style) -> ViewBox {
-        let style = Style {
-            radius: 0.0,
-            ..style.clone()
-        };
-        self.start.viewbox(&style).add(&self.end.viewbox(&style))
+        segments_viewbox(std::iter::once(*self), style).add(&vertex_indices_viewbox(
+            &[self.start, self.end],
+            false,
+            style,
+        ))
+    }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        let (start, end) = (to_f64(self.start), to_f64(self.end));
+        Some(Coord {
+            x: (start.x + end.x) / 2.0,
+            y: (start.y + end.y) / 2.0,
+        })
     }
 }
 
 impl<T: CoordNum> ToSvgStr for LineString<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        self.lines().map(|line| line.to_svg_str(style)).collect()
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
+        if self.0.len() < 2 {
+            return Ok(());
+        }
+        write!(out, r#"<path d=""#)?;
+        write_subpath(out, self, style)?;
+        write!(out, r#"" fill="none"{}/>"#, style.without_fill())?;
+        write_vertex_indices(out, &self.0, self.is_closed(), style)
     }
 
     fn viewbox(&self, style: &Style) -> ViewBox {
-        self.lines().fold(ViewBox::default(), |view_box, line| {
-            view_box.add(&line.viewbox(style))
-        })
+        segments_viewbox(self.lines(), style).add(&vertex_indices_viewbox(
+            &self.0,
+            self.is_closed(),
+            style,
+        ))
+    }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        line_string_midpoint(self)
     }
 }
 
 impl<T: CoordNum> ToSvgStr for MultiLineString<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        self.0
-            .iter()
-            .map(|line_string| line_string.to_svg_str(style))
-            .collect()
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
+        let mut line_strings = self.0.iter().filter(|line_string| line_string.0.len() > 1);
+        let first = match line_strings.next() {
+            Some(first) => first,
+            None => return Ok(()),
+        };
+        write!(out, r#"<path d=""#)?;
+        write_subpath(out, first, style)?;
+        for line_string in line_strings {
+            write!(out, " ")?;
+            write_subpath(out, line_string, style)?;
+        }
+        write!(out, r#"" fill="none"{}/>"#, style.without_fill())?;
+        for line_string in &self.0 {
+            write_vertex_indices(out, &line_string.0, line_string.is_closed(), style)?;
+        }
+        Ok(())
     }
 
     fn viewbox(&self, style: &Style) -> ViewBox {
@@ -97,70 +223,81 @@ impl<T: CoordNum> ToSvgStr for MultiLineString<T> {
                 view_box.add(&line_string.viewbox(style))
             })
     }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        self.0
+            .iter()
+            .max_by_key(|line_string| line_string.0.len())
+            .and_then(line_string_midpoint)
+    }
 }
 
 impl<T: CoordNum> ToSvgStr for Polygon<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        use std::fmt::Write;
-        let mut path = String::new();
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
+        write!(out, r#"<path fill-rule="evenodd" d=""#)?;
         for contour in std::iter::once(self.exterior()).chain(self.interiors().iter()) {
-            let mut points = contour.points_iter();
-            if let Some(first_point) = points.next() {
-                write!(path, "M {:?} {:?}", first_point.x(), first_point.y()).unwrap()
-            }
-            for point in points {
-                write!(path, " L {:?} {:?}", point.x(), point.y()).unwrap();
-            }
-            write!(path, " Z ").unwrap();
+            write_subpath(out, contour, style)?;
+            write!(out, " Z ")?;
         }
-
-        format!(
-            r#"<path fill-rule="evenodd" d="{path}"{style}/>"#,
-            path = path,
-            style = style,
-        )
+        write!(out, r#""{}/>"#, style)?;
+        for contour in std::iter::once(self.exterior()).chain(self.interiors().iter()) {
+            write_vertex_indices(out, &contour.0, true, style)?;
+        }
+        Ok(())
     }
 
     fn viewbox(&self, style: &Style) -> ViewBox {
-        self.exterior()
-            .lines()
-            .chain(
-                self.interiors()
-                    .iter()
-                    .flat_map(|interior| interior.lines()),
-            )
-            .fold(ViewBox::default(), |view_box, line_string| {
-                view_box.add(&line_string.viewbox(style))
+        std::iter::once(self.exterior())
+            .chain(self.interiors().iter())
+            .fold(ViewBox::default(), |view_box, contour| {
+                view_box
+                    .add(&segments_viewbox(contour.lines(), style))
+                    .add(&vertex_indices_viewbox(&contour.0, true, style))
             })
     }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        polygon_interior_point(self)
+    }
 }
 
 impl<T: CoordNum> ToSvgStr for Rect<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        Polygon::from(*self).to_svg_str(style)
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
+        Polygon::from(*self).write_svg(out, style)
     }
 
     fn viewbox(&self, style: &Style) -> ViewBox {
         Polygon::from(*self).viewbox(style)
     }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        let (min, max) = (to_f64(self.min()), to_f64(self.max()));
+        Some(Coord {
+            x: (min.x + max.x) / 2.0,
+            y: (min.y + max.y) / 2.0,
+        })
+    }
 }
 
 impl<T: CoordNum> ToSvgStr for Triangle<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        Polygon::new(self.to_array().iter().cloned().collect(), vec![]).to_svg_str(style)
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
+        Polygon::new(self.to_array().iter().cloned().collect(), vec![]).write_svg(out, style)
     }
 
     fn viewbox(&self, style: &Style) -> ViewBox {
         Polygon::new(self.to_array().iter().cloned().collect(), vec![]).viewbox(style)
     }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        polygon_interior_point(&self.to_polygon())
+    }
 }
 
 impl<T: CoordNum> ToSvgStr for MultiPolygon<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
         self.0
             .iter()
-            .map(|polygons| polygons.to_svg_str(style))
-            .collect()
+            .try_for_each(|polygons| polygons.write_svg(out, style))
     }
 
     fn viewbox(&self, style: &Style) -> ViewBox {
@@ -170,22 +307,33 @@ impl<T: CoordNum> ToSvgStr for MultiPolygon<T> {
                 view_box.add(&polygons.viewbox(style))
             })
     }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        self.0
+            .iter()
+            .max_by(|a, b| {
+                ring_area(a.exterior())
+                    .partial_cmp(&ring_area(b.exterior()))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .and_then(polygon_interior_point)
+    }
 }
 
 impl<T: CoordNum> ToSvgStr for Geometry<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
         use Geometry::*;
         match self {
-            Point(point) => point.to_svg_str(style),
-            Line(line) => line.to_svg_str(style),
-            LineString(line_tring) => line_tring.to_svg_str(style),
-            Triangle(triangle) => triangle.to_polygon().to_svg_str(style),
-            Rect(rect) => rect.to_polygon().to_svg_str(style),
-            Polygon(polygon) => polygon.to_svg_str(style),
-            MultiPoint(multi_point) => multi_point.to_svg_str(style),
-            MultiLineString(multi_line_string) => multi_line_string.to_svg_str(style),
-            MultiPolygon(multi_polygon) => multi_polygon.to_svg_str(style),
-            GeometryCollection(geometry_collection) => geometry_collection.to_svg_str(style),
+            Point(point) => point.write_svg(out, style),
+            Line(line) => line.write_svg(out, style),
+            LineString(line_tring) => line_tring.write_svg(out, style),
+            Triangle(triangle) => triangle.to_polygon().write_svg(out, style),
+            Rect(rect) => rect.to_polygon().write_svg(out, style),
+            Polygon(polygon) => polygon.write_svg(out, style),
+            MultiPoint(multi_point) => multi_point.write_svg(out, style),
+            MultiLineString(multi_line_string) => multi_line_string.write_svg(out, style),
+            MultiPolygon(multi_polygon) => multi_polygon.write_svg(out, style),
+            GeometryCollection(geometry_collection) => geometry_collection.write_svg(out, style),
         }
     }
 
@@ -204,14 +352,37 @@ impl<T: CoordNum> ToSvgStr for Geometry<T> {
             GeometryCollection(geometry_collection) => geometry_collection.viewbox(style),
         }
     }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        use Geometry::*;
+        match self {
+            Point(point) => point.label_anchor(),
+            Line(line) => line.label_anchor(),
+            LineString(line_string) => line_string.label_anchor(),
+            Triangle(triangle) => triangle.label_anchor(),
+            Rect(rect) => rect.label_anchor(),
+            Polygon(polygon) => polygon.label_anchor(),
+            MultiPoint(multi_point) => multi_point.label_anchor(),
+            MultiLineString(multi_line_string) => multi_line_string.label_anchor(),
+            MultiPolygon(multi_polygon) => multi_polygon.label_anchor(),
+            GeometryCollection(geometry_collection) => geometry_collection.label_anchor(),
+        }
+    }
+
+    fn is_points(&self) -> bool {
+        match self {
+            Geometry::Point(_) | Geometry::MultiPoint(_) => true,
+            Geometry::GeometryCollection(geometry_collection) => geometry_collection.is_points(),
+            _ => false,
+        }
+    }
 }
 
 impl<T: CoordNum> ToSvgStr for GeometryCollection<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
         self.0
             .iter()
-            .map(|geometry| geometry.to_svg_str(style))
-            .collect()
+            .try_for_each(|geometry| geometry.write_svg(out, style))
     }
 
     fn viewbox(&self, style: &Style) -> ViewBox {
@@ -221,13 +392,20 @@ impl<T: CoordNum> ToSvgStr for GeometryCollection<T> {
                 view_box.add(&geometry.viewbox(style))
             })
     }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        self.0.iter().find_map(|geometry| geometry.label_anchor())
+    }
+
+    fn is_points(&self) -> bool {
+        !self.0.is_empty() && self.0.iter().all(|geometry| geometry.is_points())
+    }
 }
 
-impl<'a, T: ToSvgStr> ToSvgStr for &'a [T] {
-    fn to_svg_str(&self, style: &Style) -> String {
+impl<T: ToSvgStr> ToSvgStr for [T] {
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
         self.iter()
-            .map(|geometry| geometry.to_svg_str(style))
-            .collect()
+            .try_for_each(|geometry| geometry.write_svg(out, style))
     }
 
     fn viewbox(&self, style: &Style) -> ViewBox {
@@ -235,13 +413,20 @@ impl<'a, T: ToSvgStr> ToSvgStr for &'a [T] {
             view_box.add(&item.viewbox(style))
         })
     }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        self.iter().find_map(|item| item.label_anchor())
+    }
+
+    fn is_points(&self) -> bool {
+        !self.is_empty() && self.iter().all(|item| item.is_points())
+    }
 }
 
 impl<T: ToSvgStr> ToSvgStr for Vec<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
         self.iter()
-            .map(|geometry| geometry.to_svg_str(style))
-            .collect()
+            .try_for_each(|geometry| geometry.write_svg(out, style))
     }
 
     fn viewbox(&self, style: &Style) -> ViewBox {
@@ -249,12 +434,20 @@ impl<T: ToSvgStr> ToSvgStr for Vec<T> {
             view_box.add(&item.viewbox(style))
         })
     }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        self.iter().find_map(|item| item.label_anchor())
+    }
+
+    fn is_points(&self) -> bool {
+        !self.is_empty() && self.iter().all(|item| item.is_points())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Color, ToSvg};
-    use geo_types::{LineString, Point, Polygon};
+    use crate::{Color, Style, ToSvg, ToSvgStr};
+    use geo_types::{Coord, LineString, MultiLineString, Point, Polygon};
 
     #[test]
     fn test_point() {
@@ -262,19 +455,19 @@ mod tests {
             "{}",
             Point::new(0.0, 0.0)
                 .to_svg()
-                .with_fill_color(Color::Named("red"))
+                .with_fill_color(Color::named("red").unwrap())
                 .with_radius(10.0)
-                .with_stroke_color(Color::Named("black"))
+                .with_stroke_color(Color::named("black").unwrap())
                 .and(
                     Point::new(50.0, 0.0)
                         .to_svg()
                         .with_radius(5.0)
-                        .with_stroke_color(Color::Named("blue"))
+                        .with_stroke_color(Color::named("blue").unwrap())
                 )
                 .with_stroke_width(1.0)
                 .with_opacity(0.5)
                 .with_fill_opacity(0.5)
-                .with_fill_color(Color::Named("green"))
+                .with_fill_color(Color::named("green").unwrap())
         );
     }
 
@@ -297,8 +490,47 @@ mod tests {
                 ])]
             )
             .to_svg()
-            .with_fill_color(Color::Named("black"))
-            .with_stroke_color(Color::Named("red"))
+            .with_fill_color(Color::named("black").unwrap())
+            .with_stroke_color(Color::named("red").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_line_string() {
+        let line_string = LineString::from(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 5.0)]);
+        assert_eq!(
+            line_string.to_svg_str(&Style::default()),
+            r#"<path d="M 0.0 0.0 L 10.0 0.0 L 10.0 5.0" fill="none"/>"#
+        );
+    }
+
+    #[test]
+    fn test_multi_line_string() {
+        let multi_line_string = MultiLineString(vec![
+            LineString::from(vec![(0.0, 0.0), (10.0, 0.0)]),
+            LineString::from(vec![(5.0, 5.0)]),
+            LineString::from(vec![(0.0, 10.0), (10.0, 10.0)]),
+        ]);
+        assert_eq!(
+            multi_line_string.to_svg_str(&Style::default()),
+            r#"<path d="M 0.0 0.0 L 10.0 0.0 M 0.0 10.0 L 10.0 10.0" fill="none"/>"#
+        );
+    }
+
+    #[test]
+    fn test_label_anchor() {
+        let line_string = LineString::from(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 30.0)]);
+        assert_eq!(line_string.label_anchor(), Some(Coord { x: 10.0, y: 10.0 }));
+
+        let polygon = Polygon::new(
+            LineString::from(vec![(0.0, 0.0), (30.0, 0.0), (30.0, 30.0), (0.0, 30.0)]),
+            vec![LineString::from(vec![
+                (5.0, 5.0),
+                (20.0, 5.0),
+                (20.0, 25.0),
+                (5.0, 25.0),
+            ])],
         );
+        assert_eq!(polygon.label_anchor(), Some(Coord { x: 25.0, y: 15.0 }));
     }
 }
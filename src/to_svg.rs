@@ -1,16 +1,36 @@
-use crate::{Style, Svg, ToSvgStr, ViewBox};
+use crate::{Svg, ToSvgStr};
+use std::sync::Arc;
 
+/// Converts geometries to a [`Svg`].
+///
+/// Borrowed and owned items share the same [`SvgItem`](crate::SvgItem) type so that they can be
+/// composed with [`Svg::and`], which requires the items to be `Sync` even when borrowed: a type
+/// holding an `Rc` or a `Cell` has to be wrapped in a thread-safe type to be drawn.
 pub trait ToSvg {
-    fn to_svg(&self) -> Svg;
+    /// Borrows `self` into a [`Svg`] that lives as long as the geometry.
+    fn to_svg(&self) -> Svg<'_>;
+
+    /// Moves `self` into an owned [`Svg`] which can be stored or sent to another thread.
+    fn into_svg(self) -> Svg<'static>
+    where
+        Self: Sized + Send + 'static;
 }
 
-impl<T: ToSvgStr> ToSvg for T {
-    fn to_svg(&self) -> Svg {
+impl<T: ToSvgStr + Sync> ToSvg for T {
+    fn to_svg(&self) -> Svg<'_> {
+        Svg {
+            items: vec![Arc::new(self)],
+            ..Svg::default()
+        }
+    }
+
+    fn into_svg(self) -> Svg<'static>
+    where
+        Self: Sized + Send + 'static,
+    {
         Svg {
-            items: vec![self],
-            siblings: vec![],
-            viewbox: ViewBox::default(),
-            style: Style::default(),
+            items: vec![Arc::new(self)],
+            ..Svg::default()
         }
     }
 }
@@ -1,16 +1,79 @@
 use crate::{Style, Svg, ViewBox};
+use geo_types::Coord;
+use std::fmt::{Result, Write};
 
 pub trait ToSvgStr {
-    fn to_svg_str(&self, style: &Style) -> String;
+    /// Writes the SVG elements of `self` to `out`.
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result;
+
     fn viewbox(&self, style: &Style) -> ViewBox;
-}
 
-impl<'a> ToSvgStr for Svg<'a> {
+    /// Default position of a [`Label`](crate::Label) attached to `self`, if any.
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        None
+    }
+
+    /// Whether `self` only draws points, which the legend shows with their marker.
+    fn is_points(&self) -> bool {
+        false
+    }
+
+    /// Styles drawn by `self` in addition to `style`, such as the styles of nested groups or
+    /// features, so that the document defines the markers they use.
+    fn styles(&self, style: &Style) -> Vec<Style> {
+        let _ = style;
+        Vec::new()
+    }
+
     fn to_svg_str(&self, style: &Style) -> String {
-        self.clone().with_style(style).to_string()
+        let mut svg = String::new();
+        self.write_svg(&mut svg, style).unwrap();
+        svg
+    }
+}
+
+impl<T: ToSvgStr + ?Sized> ToSvgStr for &T {
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
+        (**self).write_svg(out, style)
     }
 
     fn viewbox(&self, style: &Style) -> ViewBox {
-        self.clone().with_style(style).viewbox
+        (**self).viewbox(style)
+    }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        (**self).label_anchor()
+    }
+
+    fn is_points(&self) -> bool {
+        (**self).is_points()
+    }
+
+    fn styles(&self, style: &Style) -> Vec<Style> {
+        (**self).styles(style)
+    }
+}
+
+impl ToSvgStr for Svg<'_> {
+    fn write_svg(&self, out: &mut dyn Write, style: &Style) -> Result {
+        self.write_content(out, &self.viewbox_in(style), style)
+    }
+
+    fn viewbox(&self, style: &Style) -> ViewBox {
+        self.viewbox_in(style)
+    }
+
+    fn label_anchor(&self) -> Option<Coord<f64>> {
+        self.items.iter().find_map(|item| item.label_anchor())
+    }
+
+    fn is_points(&self) -> bool {
+        self.holds_points()
+    }
+
+    fn styles(&self, style: &Style) -> Vec<Style> {
+        let mut styles = Vec::new();
+        self.collect_styles(&mut styles, style);
+        styles
     }
 }
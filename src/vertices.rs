@@ -0,0 +1,117 @@
+use crate::{label::to_f64, precision::Num, Label, Style, TextAnchor, ViewBox};
+use geo_types::{Coord, CoordNum};
+use std::fmt::{Result, Write};
+
+/// Ratio between the font size of vertex indices and the point radius.
+const FONT_SIZE_RATIO: f32 = 4.0;
+
+/// Vertices of a ring or line string to annotate, without the closing vertex of closed rings.
+fn vertices<T: CoordNum>(coords: &[Coord<T>], closed: bool) -> &[Coord<T>] {
+    match coords {
+        [first, .., last] if closed && first == last => &coords[..coords.len() - 1],
+        _ => coords,
+    }
+}
+
+fn index_label<T: CoordNum>(
+    index: usize,
+    coords: &[Coord<T>],
+    closed: bool,
+    style: &Style,
+) -> Label {
+    let radius = style.radius() as f64;
+    let text = match index {
+        0 if closed => format!("0 {}", winding(coords, style)),
+        _ => index.to_string(),
+    };
+    Label::new(text)
+        .with_font_size(style.radius() * FONT_SIZE_RATIO)
+        .with_offset(radius * 1.5, radius * 1.5)
+        .with_text_anchor(TextAnchor::Start)
+}
+
+/// Winding of a closed ring as seen in the SVG output, where the y axis points down.
+fn winding<T: CoordNum>(coords: &[Coord<T>], style: &Style) -> &'static str {
+    let orientation = style.orientation();
+    let coords = coords
+        .iter()
+        .map(|coord| to_f64(orientation.apply(*coord)))
+        .collect::<Vec<_>>();
+    let area = coords
+        .iter()
+        .zip(coords.iter().cycle().skip(1))
+        .map(|(start, end)| start.x * end.y - end.x * start.y)
+        .sum::<f64>();
+    if area >= 0.0 {
+        "cw"
+    } else {
+        "ccw"
+    }
+}
+
+/// Writes a dot and the index of each vertex, a circle around the first one and the winding of
+/// closed rings next to its index.
+pub(crate) fn write_vertex_indices<T: CoordNum>(
+    out: &mut dyn Write,
+    coords: &[Coord<T>],
+    closed: bool,
+    style: &Style,
+) -> Result {
+    let coords = vertices(coords, closed);
+    if !style.vertex_indices() || coords.is_empty() {
+        return Ok(());
+    }
+    let radius = style.radius();
+    let n = |value: f64| Num(value, style.precision);
+    write!(out, r#"<g class="geo-svg-vertices">"#)?;
+    for (index, coord) in coords.iter().enumerate() {
+        let position = style.orientation().apply(*coord);
+        if index == 0 {
+            write!(
+                out,
+                r#"<circle cx="{}" cy="{}" r="{}" fill="none" stroke="red" stroke-width="{}"/>"#,
+                n(position.x),
+                n(position.y),
+                radius * 2.0,
+                radius / 2.0,
+            )?;
+        }
+        write!(
+            out,
+            r#"<circle cx="{}" cy="{}" r="{}" fill="black" stroke="none"/>"#,
+            n(position.x),
+            n(position.y),
+            radius / 2.0,
+        )?;
+        index_label(index, coords, closed, style).write(out, Some(to_f64(*coord)), style)?;
+    }
+    write!(out, "</g>")
+}
+
+/// Bounds of the annotations written by [`write_vertex_indices`].
+pub(crate) fn vertex_indices_viewbox<T: CoordNum>(
+    coords: &[Coord<T>],
+    closed: bool,
+    style: &Style,
+) -> ViewBox {
+    let coords = vertices(coords, closed);
+    if !style.vertex_indices() {
+        return ViewBox::default();
+    }
+    let radius = style.radius() as f64 * 2.5;
+    coords
+        .iter()
+        .enumerate()
+        .fold(ViewBox::default(), |viewbox, (index, coord)| {
+            let coord = to_f64(*coord);
+            let position = style.orientation().apply(coord);
+            viewbox
+                .add(&ViewBox::new(
+                    position.x - radius,
+                    position.y - radius,
+                    position.x + radius,
+                    position.y + radius,
+                ))
+                .add(&index_label(index, coords, closed, style).viewbox(Some(coord), style))
+        })
+}
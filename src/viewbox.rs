@@ -1,13 +1,16 @@
+use geo_types::{CoordNum, Rect};
+use num_traits::NumCast;
+
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
 pub struct ViewBox {
-    pub min_x: Option<f32>,
-    pub min_y: Option<f32>,
-    pub max_x: Option<f32>,
-    pub max_y: Option<f32>,
+    pub min_x: Option<f64>,
+    pub min_y: Option<f64>,
+    pub max_x: Option<f64>,
+    pub max_y: Option<f64>,
 }
 
 impl ViewBox {
-    pub fn new(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Self {
+    pub fn new(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Self {
         Self {
             min_x: Some(min_x),
             min_y: Some(min_y),
@@ -25,31 +28,31 @@ impl ViewBox {
         }
     }
 
-    pub fn min_x(&self) -> f32 {
+    pub fn min_x(&self) -> f64 {
         self.min_x.unwrap_or_default()
     }
 
-    pub fn min_y(&self) -> f32 {
+    pub fn min_y(&self) -> f64 {
         self.min_y.unwrap_or_default()
     }
 
-    pub fn max_x(&self) -> f32 {
+    pub fn max_x(&self) -> f64 {
         self.max_x.unwrap_or_default()
     }
 
-    pub fn max_y(&self) -> f32 {
+    pub fn max_y(&self) -> f64 {
         self.max_y.unwrap_or_default()
     }
 
-    pub fn width(&self) -> f32 {
+    pub fn width(&self) -> f64 {
         (self.min_x() - self.max_x()).abs()
     }
 
-    pub fn height(&self) -> f32 {
+    pub fn height(&self) -> f64 {
         (self.min_y() - self.max_y()).abs()
     }
 
-    fn min_option(a: Option<f32>, b: Option<f32>) -> Option<f32> {
+    fn min_option(a: Option<f64>, b: Option<f64>) -> Option<f64> {
         match (a, b) {
             (Some(a), Some(b)) => Some(a.min(b)),
             (Some(a), None) => Some(a),
@@ -58,7 +61,7 @@ impl ViewBox {
         }
     }
 
-    fn max_option(a: Option<f32>, b: Option<f32>) -> Option<f32> {
+    fn max_option(a: Option<f64>, b: Option<f64>) -> Option<f64> {
         match (a, b) {
             (Some(a), Some(b)) => Some(a.max(b)),
             (Some(a), None) => Some(a),
@@ -67,11 +70,100 @@ impl ViewBox {
         }
     }
 
-    pub fn with_margin(mut self, margin: f32) -> Self {
-        self.min_x = self.min_x.map(|x| x - margin);
-        self.min_y = self.min_y.map(|y| y - margin);
-        self.max_x = self.max_x.map(|x| x + margin);
-        self.max_y = self.max_y.map(|y| y + margin);
+    pub fn with_margin(self, margin: f64) -> Self {
+        self.with_margins(margin, margin)
+    }
+
+    /// Adds `horizontal` on the left and right and `vertical` on the top and bottom.
+    pub fn with_margins(mut self, horizontal: f64, vertical: f64) -> Self {
+        self.min_x = self.min_x.map(|x| x - horizontal);
+        self.min_y = self.min_y.map(|y| y - vertical);
+        self.max_x = self.max_x.map(|x| x + horizontal);
+        self.max_y = self.max_y.map(|y| y + vertical);
         self
     }
+
+    /// Adds a margin proportional to the size on each side, `0.1` adding 10% of the width on
+    /// the left and on the right and 10% of the height on the top and on the bottom.
+    pub fn with_relative_margin(self, ratio: f64) -> Self {
+        self.with_relative_margins(ratio, ratio)
+    }
+
+    /// Adds a margin proportional to the width on the left and right and to the height on the
+    /// top and bottom.
+    pub fn with_relative_margins(self, horizontal: f64, vertical: f64) -> Self {
+        let (width, height) = (self.width(), self.height());
+        self.with_margins(width * horizontal, height * vertical)
+    }
+
+    /// Grows the viewbox around its center so that it is at least `width` by `height`, which
+    /// keeps a single point or a straight line from yielding an empty dimension.
+    pub fn with_min_size(self, width: f64, height: f64) -> Self {
+        let horizontal = (width - self.width()).max(0.0) / 2.0;
+        let vertical = (height - self.height()).max(0.0) / 2.0;
+        self.with_margins(horizontal, vertical)
+    }
+}
+
+impl<T: CoordNum> From<Rect<T>> for ViewBox {
+    fn from(rect: Rect<T>) -> Self {
+        let value = |value: T| NumCast::from(value).unwrap_or(0.0);
+        Self::new(
+            value(rect.min().x),
+            value(rect.min().y),
+            value(rect.max().x),
+            value(rect.max().y),
+        )
+    }
+}
+
+/// Space added around the bounds of a document.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Margin {
+    /// Horizontal and vertical margins in the units of the geometries.
+    Absolute(f64, f64),
+    /// Horizontal and vertical margins as ratios of the width and height.
+    Relative(f64, f64),
+}
+
+impl Margin {
+    pub fn apply(&self, viewbox: ViewBox) -> ViewBox {
+        match *self {
+            Margin::Absolute(horizontal, vertical) => viewbox.with_margins(horizontal, vertical),
+            Margin::Relative(horizontal, vertical) => {
+                viewbox.with_relative_margins(horizontal, vertical)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Margin, ViewBox};
+
+    #[test]
+    fn test_margins() {
+        let viewbox = ViewBox::new(0.0, 0.0, 100.0, 10.0);
+        assert_eq!(
+            Margin::Relative(0.1, 0.5).apply(viewbox),
+            ViewBox::new(-10.0, -5.0, 110.0, 15.0)
+        );
+        assert_eq!(
+            viewbox.with_margins(1.0, 2.0),
+            ViewBox::new(-1.0, -2.0, 101.0, 12.0)
+        );
+    }
+
+    #[test]
+    fn test_min_size() {
+        let vertical_line = ViewBox::new(5.0, 0.0, 5.0, 10.0);
+        assert_eq!(
+            vertical_line.with_min_size(2.0, 2.0),
+            ViewBox::new(4.0, 0.0, 6.0, 10.0)
+        );
+        assert_eq!(
+            ViewBox::default().with_min_size(2.0, 2.0),
+            ViewBox::default()
+        );
+    }
 }
@@ -0,0 +1,22 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// Escapes a string for use in XML text and attribute values.
+pub(crate) struct Escaped<'a>(pub &'a str);
+
+impl Display for Escaped<'_> {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        let mut rest = self.0;
+        while let Some(index) = rest.find(['&', '<', '>', '"', '\'']) {
+            fmt.write_str(&rest[..index])?;
+            fmt.write_str(match rest.as_bytes()[index] {
+                b'&' => "&amp;",
+                b'<' => "&lt;",
+                b'>' => "&gt;",
+                b'"' => "&quot;",
+                _ => "&apos;",
+            })?;
+            rest = &rest[index + 1..];
+        }
+        fmt.write_str(rest)
+    }
+}